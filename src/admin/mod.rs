@@ -0,0 +1,661 @@
+use std::{
+    fs,
+    io::{self, Error, ErrorKind},
+    net::SocketAddr,
+    sync::Arc,
+};
+
+use aws_sdk_s3::Client as S3Client;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server as HyperServer, StatusCode,
+};
+use log::info;
+use serde::Serialize;
+use tokio::sync::RwLock;
+
+use crate::{manifest_plugins_dir, utils::time, Spec, StorageNamespace};
+
+/// Static OpenAPI 3.0 schema for every route this server exposes, served
+/// as-is from "GET /v2/openapi.json" so external tooling/dashboards can
+/// generate a client instead of hand-parsing these handlers.
+const OPENAPI_SCHEMA: &str = include_str!("openapi.json");
+
+/// JSON body returned for any non-2xx response, so every error from the
+/// admin API has the same shape regardless of which route produced it.
+#[derive(Debug, Serialize)]
+struct ErrorMsg {
+    code: u16,
+    message: String,
+}
+
+/// Body for "PUT /v2/machine". Only "non_anchor_nodes" is mutable through
+/// this endpoint today -- resizing anchor nodes changes the genesis
+/// initial-stakers list and isn't safe to do live.
+#[derive(Debug, serde::Deserialize)]
+struct MachineUpdate {
+    non_anchor_nodes: u32,
+}
+
+/// Body for "PUT /v2/events/update-artifacts". Paths are resolved on this
+/// daemon's own filesystem (the same machine running "avalanche-ops"),
+/// mirroring how "Spec.install_artifacts" already stores local paths.
+/// Either field may be omitted to only update the other artifact.
+#[derive(Debug, serde::Deserialize, Default)]
+struct UpdateArtifactsRequest {
+    #[serde(default)]
+    avalanche_bin_path: Option<String>,
+    #[serde(default)]
+    plugins_dir: Option<String>,
+}
+
+/// "describeDaemon"-style health payload for "GET /v2/status".
+#[derive(Debug, Serialize)]
+struct Status {
+    healthy: bool,
+    spec_id: String,
+    spec_file_path: String,
+}
+
+/// Which "StorageNamespace::Discover*" state to list nodes from, parsed
+/// from "GET /v2/nodes?state=...". Omitting "state" lists every node
+/// regardless of discovery phase.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NodeState {
+    All,
+    Provisioning,
+    Bootstrapping,
+    Ready,
+}
+
+impl NodeState {
+    fn parse(raw: Option<&str>) -> io::Result<Self> {
+        match raw {
+            None => Ok(NodeState::All),
+            Some("provisioning") => Ok(NodeState::Provisioning),
+            Some("bootstrapping") => Ok(NodeState::Bootstrapping),
+            Some("ready") => Ok(NodeState::Ready),
+            Some(other) => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "unknown 'state' query value '{}' (want one of provisioning|bootstrapping|ready)",
+                    other
+                ),
+            )),
+        }
+    }
+
+    /// "StorageNamespace" discovery prefixes to list for this state.
+    fn prefixes(&self, id: &str) -> Vec<String> {
+        let all = [
+            StorageNamespace::DiscoverProvisioningAnchorNodesDir(id.to_string()).encode(),
+            StorageNamespace::DiscoverProvisioningNonAnchorNodesDir(id.to_string()).encode(),
+            StorageNamespace::DiscoverBootstrappingAnchorNodesDir(id.to_string()).encode(),
+            StorageNamespace::DiscoverReadyAnchorNodesDir(id.to_string()).encode(),
+            StorageNamespace::DiscoverReadyNonAnchorNodesDir(id.to_string()).encode(),
+        ];
+        match self {
+            NodeState::All => all.to_vec(),
+            NodeState::Provisioning => all[0..2].to_vec(),
+            NodeState::Bootstrapping => all[2..3].to_vec(),
+            NodeState::Ready => all[3..5].to_vec(),
+        }
+    }
+}
+
+/// Embedded HTTP admin API for a running deployment, so consumers can
+/// introspect a cluster's deployment/discovery state and rescale it
+/// without shelling out to re-read/re-write the spec file or list S3
+/// themselves. Serves:
+///   GET /v2/spec                    -- the full "Spec", JSON-encoded
+///   GET /v2/nodes?state=...         -- "StorageNamespace::Discover*" nodes,
+///                                      optionally filtered to
+///                                      "provisioning"|"bootstrapping"|"ready"
+///   GET /v2/endpoints               -- "Spec.endpoints"
+///   GET /v2/genesis                 -- proxies "StorageNamespace::GenesisFile"
+///   PUT /v2/machine                 -- rescales "machine.non_anchor_nodes"
+///   PUT /v2/events/update-artifacts -- uploads a new avalanche.zstd/plugins
+///                                      bundle and writes the
+///                                      "EventsUpdateArtifactsEvent" marker
+///   GET /v2/status                  -- liveness/identity of this daemon
+///   GET /v2/openapi.json            -- machine-readable schema of the above
+///
+/// The live "Spec" is held behind a lock and re-synced to
+/// "spec_file_path" on every mutation, so the file on disk and the
+/// in-memory state a caller sees never drift apart. "StorageNamespace::encode"
+/// is the single source of truth behind every S3-backed handler here, so a
+/// key layout change only ever needs to happen in one place.
+pub struct AdminServer {
+    spec: Arc<RwLock<Spec>>,
+    spec_file_path: String,
+    s3_cli: S3Client,
+    s3_bucket: String,
+}
+
+impl AdminServer {
+    pub fn new(spec: Spec, spec_file_path: String, s3_cli: S3Client, s3_bucket: String) -> Self {
+        Self {
+            spec: Arc::new(RwLock::new(spec)),
+            spec_file_path,
+            s3_cli,
+            s3_bucket,
+        }
+    }
+
+    /// Binds and serves the admin API until the process is killed.
+    pub async fn serve(self, addr: SocketAddr) -> io::Result<()> {
+        let spec = self.spec;
+        let spec_file_path = Arc::new(self.spec_file_path);
+        let s3_cli = Arc::new(self.s3_cli);
+        let s3_bucket = Arc::new(self.s3_bucket);
+
+        let make_svc = make_service_fn(move |_conn| {
+            let spec = spec.clone();
+            let spec_file_path = spec_file_path.clone();
+            let s3_cli = s3_cli.clone();
+            let s3_bucket = s3_bucket.clone();
+            async move {
+                Ok::<_, hyper::Error>(service_fn(move |req| {
+                    handle(req, spec.clone(), spec_file_path.clone(), s3_cli.clone(), s3_bucket.clone())
+                }))
+            }
+        });
+
+        info!("serving admin API on {}", addr);
+        HyperServer::bind(&addr)
+            .serve(make_svc)
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("admin API server failed ({})", e)))
+    }
+}
+
+async fn handle(
+    req: Request<Body>,
+    spec: Arc<RwLock<Spec>>,
+    spec_file_path: Arc<String>,
+    s3_cli: Arc<S3Client>,
+    s3_bucket: Arc<String>,
+) -> Result<Response<Body>, hyper::Error> {
+    let query = req.uri().query().map(|q| q.to_string());
+    let resp = match (req.method(), req.uri().path()) {
+        (&Method::GET, "/v2/spec") => {
+            let spec = spec.read().await;
+            json_response(StatusCode::OK, &*spec)
+        }
+        (&Method::GET, "/v2/nodes") => get_nodes(query.as_deref(), spec, s3_cli, s3_bucket).await,
+        (&Method::GET, "/v2/endpoints") => {
+            let spec = spec.read().await;
+            json_response(StatusCode::OK, &spec.endpoints)
+        }
+        (&Method::GET, "/v2/genesis") => get_genesis(spec, s3_cli, s3_bucket).await,
+        (&Method::GET, "/v2/status") => {
+            let spec = spec.read().await;
+            json_response(
+                StatusCode::OK,
+                &Status {
+                    healthy: true,
+                    spec_id: spec.id.clone(),
+                    spec_file_path: (*spec_file_path).clone(),
+                },
+            )
+        }
+        (&Method::GET, "/v2/openapi.json") => Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(Body::from(OPENAPI_SCHEMA))
+            .expect("unexpected response build failure"),
+        (&Method::PUT, "/v2/machine") => put_machine(req, spec, spec_file_path).await,
+        (&Method::PUT, "/v2/events/update-artifacts") => {
+            put_update_artifacts(req, spec, s3_cli, s3_bucket).await
+        }
+        _ => error_response(StatusCode::NOT_FOUND, "route not found"),
+    };
+    Ok(resp)
+}
+
+/// Parses "?state=" out of the raw query string (there being only ever
+/// this one query parameter on this route).
+fn parse_state_query(query: Option<&str>) -> io::Result<NodeState> {
+    let raw = query.and_then(|q| {
+        q.split('&')
+            .find_map(|kv| kv.strip_prefix("state=").map(|v| v.to_string()))
+    });
+    NodeState::parse(raw.as_deref())
+}
+
+async fn get_nodes(
+    query: Option<&str>,
+    spec: Arc<RwLock<Spec>>,
+    s3_cli: Arc<S3Client>,
+    s3_bucket: Arc<String>,
+) -> Response<Body> {
+    let state = match parse_state_query(query) {
+        Ok(s) => s,
+        Err(e) => return error_response(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+
+    let id = spec.read().await.id.clone();
+    let mut nodes = Vec::new();
+    for prefix in state.prefixes(&id) {
+        let keys = match list_keys(&s3_cli, &s3_bucket, &prefix).await {
+            Ok(k) => k,
+            Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        };
+        for key in keys {
+            match StorageNamespace::parse_node_from_path(&key) {
+                Ok(node) => nodes.push(node),
+                Err(e) => info!("skipping undecodable discovery key {} ({})", key, e),
+            }
+        }
+    }
+
+    json_response(StatusCode::OK, &nodes)
+}
+
+async fn get_genesis(
+    spec: Arc<RwLock<Spec>>,
+    s3_cli: Arc<S3Client>,
+    s3_bucket: Arc<String>,
+) -> Response<Body> {
+    let id = spec.read().await.id.clone();
+    let key = StorageNamespace::GenesisFile(id).encode();
+    let obj = match s3_cli.get_object().bucket(&*s3_bucket).key(&key).send().await {
+        Ok(obj) => obj,
+        Err(e) => {
+            return error_response(
+                StatusCode::NOT_FOUND,
+                &format!("failed to fetch genesis s3://{}/{} ({})", s3_bucket, key, e),
+            )
+        }
+    };
+    let body = match hyper::body::to_bytes(Body::wrap_stream(obj.body)).await {
+        Ok(b) => b,
+        Err(e) => {
+            return error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                &format!("failed to read genesis body ({})", e),
+            )
+        }
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header("content-type", "application/json")
+        .body(Body::from(body))
+        .expect("unexpected response build failure")
+}
+
+async fn put_machine(
+    req: Request<Body>,
+    spec: Arc<RwLock<Spec>>,
+    spec_file_path: Arc<String>,
+) -> Response<Body> {
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(b) => b,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("failed to read request body ({})", e),
+            )
+        }
+    };
+    let update: MachineUpdate = match serde_json::from_slice(&body) {
+        Ok(u) => u,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("invalid JSON body ({})", e),
+            )
+        }
+    };
+
+    let mut spec = spec.write().await;
+    let old_non_anchor_nodes = spec.machine.non_anchor_nodes;
+    let old_node_region_assignment = spec.node_region_assignment.clone();
+    spec.machine.non_anchor_nodes = update.non_anchor_nodes;
+    if let Err(e) = spec.plan_regions() {
+        spec.machine.non_anchor_nodes = old_non_anchor_nodes;
+        spec.node_region_assignment = old_node_region_assignment;
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("rejected machine update ({})", e),
+        );
+    }
+    if let Err(e) = spec.validate() {
+        spec.machine.non_anchor_nodes = old_non_anchor_nodes;
+        spec.node_region_assignment = old_node_region_assignment;
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            &format!("rejected machine update ({})", e),
+        );
+    }
+    if let Err(e) = spec.sync(&spec_file_path) {
+        spec.machine.non_anchor_nodes = old_non_anchor_nodes;
+        spec.node_region_assignment = old_node_region_assignment;
+        return error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("failed to persist spec ({})", e),
+        );
+    }
+    info!(
+        "rescaled non_anchor_nodes to {} via PUT /v2/machine",
+        spec.machine.non_anchor_nodes
+    );
+
+    json_response(StatusCode::OK, &*spec)
+}
+
+/// Uploads the artifacts named in the request body, then writes the
+/// "EventsUpdateArtifactsEvent" marker last -- "avalanched" watches for
+/// that marker and must only ever see it after the artifacts it triggers
+/// on are already fully uploaded.
+async fn put_update_artifacts(
+    req: Request<Body>,
+    spec: Arc<RwLock<Spec>>,
+    s3_cli: Arc<S3Client>,
+    s3_bucket: Arc<String>,
+) -> Response<Body> {
+    let body = match hyper::body::to_bytes(req.into_body()).await {
+        Ok(b) => b,
+        Err(e) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                &format!("failed to read request body ({})", e),
+            )
+        }
+    };
+    let update: UpdateArtifactsRequest = if body.is_empty() {
+        UpdateArtifactsRequest::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(u) => u,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("invalid JSON body ({})", e),
+                )
+            }
+        }
+    };
+
+    let id = spec.read().await.id.clone();
+
+    if let Some(avalanche_bin_path) = &update.avalanche_bin_path {
+        let bytes = match fs::read(avalanche_bin_path) {
+            Ok(b) => b,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("failed to read {} ({})", avalanche_bin_path, e),
+                )
+            }
+        };
+        let key =
+            StorageNamespace::EventsUpdateArtifactsInstallDirAvalancheBinCompressed(id.clone())
+                .encode();
+        if let Err(e) = put_object(&s3_cli, &s3_bucket, &key, bytes).await {
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string());
+        }
+    }
+
+    if let Some(plugins_dir) = &update.plugins_dir {
+        let artifacts = match manifest_plugins_dir(plugins_dir) {
+            Ok(a) => a,
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_REQUEST,
+                    &format!("failed to manifest {} ({})", plugins_dir, e),
+                )
+            }
+        };
+        let plugins_dir_key =
+            StorageNamespace::EventsUpdateArtifactsInstallDirPluginsDir(id.clone()).encode();
+        for artifact in artifacts {
+            let path = format!("{}/{}", plugins_dir.trim_end_matches('/'), artifact.file_name);
+            let bytes = match fs::read(&path) {
+                Ok(b) => b,
+                Err(e) => {
+                    return error_response(
+                        StatusCode::BAD_REQUEST,
+                        &format!("failed to read {} ({})", path, e),
+                    )
+                }
+            };
+            let key = format!("{}/{}", plugins_dir_key, artifact.file_name);
+            if let Err(e) = put_object(&s3_cli, &s3_bucket, &key, bytes).await {
+                return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string());
+            }
+        }
+    }
+
+    let event_key = StorageNamespace::EventsUpdateArtifactsEvent(id.clone()).encode();
+    if let Err(e) = put_object(
+        &s3_cli,
+        &s3_bucket,
+        &event_key,
+        time::get(0).into_bytes(),
+    )
+    .await
+    {
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string());
+    }
+    info!("triggered artifact update for {} via PUT /v2/events/update-artifacts", id);
+
+    json_response(StatusCode::OK, &Status {
+        healthy: true,
+        spec_id: id,
+        spec_file_path: String::new(),
+    })
+}
+
+async fn put_object(
+    s3_cli: &S3Client,
+    s3_bucket: &str,
+    key: &str,
+    body: Vec<u8>,
+) -> io::Result<()> {
+    s3_cli
+        .put_object()
+        .bucket(s3_bucket)
+        .key(key)
+        .body(aws_sdk_s3::types::ByteStream::from(body))
+        .send()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to upload s3://{}/{} ({})", s3_bucket, key, e),
+            )
+        })?;
+    Ok(())
+}
+
+async fn list_keys(s3_cli: &S3Client, s3_bucket: &str, prefix: &str) -> io::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let mut req = s3_cli.list_objects_v2().bucket(s3_bucket).prefix(prefix);
+        if let Some(token) = &continuation_token {
+            req = req.continuation_token(token);
+        }
+        let resp = req.send().await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to list s3://{}/{} ({})", s3_bucket, prefix, e),
+            )
+        })?;
+
+        for obj in resp.contents.unwrap_or_default() {
+            if let Some(key) = obj.key {
+                keys.push(key);
+            }
+        }
+
+        if resp.is_truncated {
+            continuation_token = resp.next_continuation_token;
+        } else {
+            break;
+        }
+    }
+    Ok(keys)
+}
+
+#[tokio::test]
+async fn test_put_machine_rolls_back_on_invalid_update() {
+    use std::io::Write;
+    use crate::utils::random;
+
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    f.write_all(&[0]).unwrap();
+    let avalanched_bin = f.path().to_str().unwrap().to_string();
+
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    f.write_all(&[0]).unwrap();
+    let avalanchego_bin = f.path().to_str().unwrap().to_string();
+
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let plugin_path = tmp_dir.path().join(random::string(10));
+    std::fs::File::create(&plugin_path).unwrap().write_all(&[0]).unwrap();
+    let plugins_dir = tmp_dir.path().as_os_str().to_str().unwrap().to_string();
+
+    let id = random::string(10);
+    let bucket = format!("test-{}", time::get(8));
+    let contents = format!(
+        r#"
+id: {}
+
+infra:
+  aws:
+    region: us-west-2
+    s3_bucket: {}
+
+machine:
+  non_anchor_nodes: 5
+  instance_types:
+  - m5.large
+
+install_artifacts:
+  avalanched_bin: {}
+  avalanchego_bin: {}
+  plugins_dir: {}
+
+avalanchego_config:
+  config-file: /etc/avalanche.config.json
+  network-id: 1
+  db-type: leveldb
+  db-dir: /avalanche-data
+  log-dir: /var/log/avalanche
+  log-level: INFO
+  http-port: 9650
+  http-host: 0.0.0.0
+  http-tls-enabled: false
+  staking-enabled: true
+  staking-port: 9651
+  staking-tls-key-file: "/etc/pki/tls/certs/avalanched.pki.key"
+  staking-tls-cert-file: "/etc/pki/tls/certs/avalanched.pki.crt"
+  snow-sample-size: 20
+  snow-quorum-size: 15
+  index-enabled: false
+  index-allow-incomplete: false
+  api-admin-enabled: true
+  api-info-enabled: true
+  api-keystore-enabled: true
+  api-metrics-enabled: true
+  api-health-enabled: true
+  api-ipcs-enabled: true
+  chain-config-dir: /etc/avalanche/configs/chains
+  subnet-config-dir: /etc/avalanche/configs/subnets
+  profile-dir: /var/log/avalanche-profile/avalanche
+
+coreth_config:
+  coreth-admin-api-enabled: true
+  metrics-enabled: true
+  log-level: "info"
+"#,
+        id, bucket, avalanched_bin, avalanchego_bin, plugins_dir,
+    );
+    let mut f = tempfile::NamedTempFile::new().unwrap();
+    f.write_all(contents.as_bytes()).unwrap();
+    let config_path = f.path().to_str().unwrap().to_string();
+
+    let cfg = Spec::load(&config_path).unwrap();
+    let spec = Arc::new(RwLock::new(cfg));
+    let spec_file_path = Arc::new(config_path);
+
+    // a valid update is applied and persisted
+    let req = Request::builder()
+        .method(Method::PUT)
+        .uri("/v2/machine")
+        .body(Body::from(r#"{"non_anchor_nodes":8}"#))
+        .unwrap();
+    let resp = put_machine(req, spec.clone(), spec_file_path.clone()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(spec.read().await.machine.non_anchor_nodes, 8);
+
+    // an update that fails "validate" (below "MIN_MACHINE_NON_ANCHOR_NODES")
+    // is rejected and rolls the spec back to its last-good value, not left
+    // partially applied
+    let req = Request::builder()
+        .method(Method::PUT)
+        .uri("/v2/machine")
+        .body(Body::from(r#"{"non_anchor_nodes":0}"#))
+        .unwrap();
+    let resp = put_machine(req, spec.clone(), spec_file_path.clone()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(spec.read().await.machine.non_anchor_nodes, 8);
+}
+
+#[test]
+fn test_node_state_parse() {
+    assert_eq!(NodeState::parse(None).unwrap(), NodeState::All);
+    assert_eq!(NodeState::parse(Some("provisioning")).unwrap(), NodeState::Provisioning);
+    assert_eq!(NodeState::parse(Some("bootstrapping")).unwrap(), NodeState::Bootstrapping);
+    assert_eq!(NodeState::parse(Some("ready")).unwrap(), NodeState::Ready);
+    assert!(NodeState::parse(Some("bogus")).is_err());
+}
+
+#[test]
+fn test_node_state_prefixes() {
+    assert_eq!(NodeState::All.prefixes("test-id").len(), 5);
+    assert_eq!(NodeState::Provisioning.prefixes("test-id").len(), 2);
+    assert_eq!(NodeState::Bootstrapping.prefixes("test-id").len(), 1);
+    assert_eq!(NodeState::Ready.prefixes("test-id").len(), 2);
+}
+
+#[test]
+fn test_parse_state_query() {
+    assert_eq!(parse_state_query(None).unwrap(), NodeState::All);
+    assert_eq!(parse_state_query(Some("state=ready")).unwrap(), NodeState::Ready);
+    assert_eq!(
+        parse_state_query(Some("foo=bar&state=provisioning")).unwrap(),
+        NodeState::Provisioning
+    );
+    assert!(parse_state_query(Some("state=bogus")).is_err());
+}
+
+fn json_response<T: Serialize>(status: StatusCode, body: &T) -> Response<Body> {
+    match serde_json::to_vec(body) {
+        Ok(b) => Response::builder()
+            .status(status)
+            .header("content-type", "application/json")
+            .body(Body::from(b))
+            .expect("unexpected response build failure"),
+        Err(e) => error_response(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            &format!("failed to serialize response ({})", e),
+        ),
+    }
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response<Body> {
+    let msg = ErrorMsg {
+        code: status.as_u16(),
+        message: message.to_string(),
+    };
+    let b = serde_json::to_vec(&msg).unwrap_or_else(|_| b"{}".to_vec());
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(b))
+        .expect("unexpected response build failure")
+}
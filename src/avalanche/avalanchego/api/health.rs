@@ -1,7 +1,6 @@
 use std::{
     collections::HashMap,
     io::{self, Error, ErrorKind},
-    process::Command,
     string::String,
     sync::Arc,
     time::Duration,
@@ -93,44 +92,19 @@ pub async fn check(u: Arc<String>, liveness: bool) -> io::Result<Response> {
     };
     info!("checking {}/{}", u, url_path);
 
-    let resp = {
-        if u.starts_with("https") {
-            let joined = http::join_uri(u.as_str(), url_path)?;
-
-            // TODO: implement this with native Rust
-            info!("sending via curl --insecure");
-            let mut cmd = Command::new("curl");
-            cmd.arg("--insecure");
-            cmd.arg(joined.as_str());
-
-            let output = cmd.output()?;
-            match serde_json::from_slice(&output.stdout) {
-                Ok(p) => p,
-                Err(e) => {
-                    return Err(Error::new(
-                        ErrorKind::Other,
-                        format!("failed to decode {}", e),
-                    ));
-                }
-            }
-        } else {
-            let req = http::create_get(u.as_str(), url_path)?;
-            let buf =
-                match http::read_bytes(req, Duration::from_secs(5), u.starts_with("https"), false)
-                    .await
-                {
-                    Ok(u) => u,
-                    Err(e) => return Err(e),
-                };
-            match serde_json::from_slice(&buf) {
-                Ok(p) => p,
-                Err(e) => {
-                    return Err(Error::new(
-                        ErrorKind::Other,
-                        format!("failed to decode {}", e),
-                    ));
-                }
-            }
+    let https = u.starts_with("https");
+    let req = http::create_get(u.as_str(), url_path)?;
+    // for HTTPS endpoints, skip certificate verification to reproduce the
+    // old "curl --insecure" behavior (e.g. self-signed certs on freshly
+    // bootstrapped nodes); HTTP endpoints are unaffected by this flag.
+    let buf = http::read_bytes(req, Duration::from_secs(5), https, https).await?;
+    let resp = match serde_json::from_slice(&buf) {
+        Ok(p) => p,
+        Err(e) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("failed to decode {}", e),
+            ));
         }
     };
     Ok(resp)
@@ -0,0 +1,225 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+    time::Duration,
+};
+
+use aws_sdk_cloudformation::{
+    model::{Capability, OnFailure, Output, Parameter, StackStatus, Tag},
+    Client,
+};
+use aws_types::SdkConfig;
+use log::info;
+use tokio::time::sleep;
+
+use crate::aws::placement::Assignment;
+
+/// A minimal view of a CloudFormation stack returned by `create_stack` and
+/// `poll_stack`.
+#[derive(Debug, Clone)]
+pub struct Stack {
+    pub name: String,
+    pub status: StackStatus,
+    pub outputs: Option<Vec<Output>>,
+}
+
+/// Thin wrapper around the CloudFormation SDK client for creating,
+/// polling, and tearing down the stacks that back an avalanche-ops
+/// deployment (VPC, EC2 instance role, anchor/non-anchor node ASGs).
+pub struct Manager {
+    cli: Client,
+}
+
+impl Manager {
+    pub fn new(shared_config: &SdkConfig) -> Self {
+        Self {
+            cli: Client::new(shared_config),
+        }
+    }
+
+    /// Creates a stack from `template_body`, returning once CloudFormation
+    /// has accepted the request (not once it has finished converging --
+    /// call `poll_stack` to wait for that).
+    pub async fn create_stack(
+        &self,
+        stack_name: &str,
+        capabilities: Option<Vec<Capability>>,
+        on_failure: OnFailure,
+        template_body: &str,
+        tags: Option<Vec<Tag>>,
+        parameters: Option<Vec<Parameter>>,
+    ) -> io::Result<Stack> {
+        info!("creating stack {}", stack_name);
+        let mut req = self
+            .cli
+            .create_stack()
+            .stack_name(stack_name)
+            .on_failure(on_failure)
+            .template_body(template_body);
+        if let Some(capabilities) = capabilities {
+            req = req.set_capabilities(Some(capabilities));
+        }
+        if let Some(tags) = tags {
+            req = req.set_tags(Some(tags));
+        }
+        if let Some(parameters) = parameters {
+            req = req.set_parameters(Some(parameters));
+        }
+        req.send().await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to create stack {} ({})", stack_name, e),
+            )
+        })?;
+
+        Ok(Stack {
+            name: stack_name.to_string(),
+            status: StackStatus::CreateInProgress,
+            outputs: None,
+        })
+    }
+
+    /// Deletes a stack. Missing stacks are treated as already deleted.
+    pub async fn delete_stack(&self, stack_name: &str) -> io::Result<()> {
+        info!("deleting stack {}", stack_name);
+        match self.cli.delete_stack().stack_name(stack_name).send().await {
+            Ok(_) => Ok(()),
+            Err(e) => {
+                let msg = e.to_string();
+                if msg.contains("does not exist") {
+                    return Ok(());
+                }
+                Err(Error::new(
+                    ErrorKind::Other,
+                    format!("failed to delete stack {} ({})", stack_name, e),
+                ))
+            }
+        }
+    }
+
+    /// Polls a stack's status every `poll_interval` until it reaches
+    /// `target_status` or `timeout` elapses.
+    pub async fn poll_stack(
+        &self,
+        stack_name: &str,
+        target_status: StackStatus,
+        timeout: Duration,
+        poll_interval: Duration,
+    ) -> io::Result<Stack> {
+        let started = tokio::time::Instant::now();
+        loop {
+            let resp = self
+                .cli
+                .describe_stacks()
+                .stack_name(stack_name)
+                .send()
+                .await
+                .map_err(|e| {
+                    Error::new(
+                        ErrorKind::Other,
+                        format!("failed to describe stack {} ({})", stack_name, e),
+                    )
+                })?;
+            let stack = resp
+                .stacks
+                .as_ref()
+                .and_then(|s| s.first())
+                .cloned()
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::NotFound,
+                        format!("stack {} not found", stack_name),
+                    )
+                })?;
+            let status = stack.stack_status.clone().unwrap_or(StackStatus::Unknown);
+            info!("stack {} status {:?}", stack_name, status);
+
+            if status == target_status {
+                return Ok(Stack {
+                    name: stack_name.to_string(),
+                    status,
+                    outputs: stack.outputs,
+                });
+            }
+            if started.elapsed() >= timeout {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    format!(
+                        "stack {} did not reach {:?} after {:?} (last status {:?})",
+                        stack_name, target_status, timeout, status
+                    ),
+                ));
+            }
+            sleep(poll_interval).await;
+        }
+    }
+}
+
+/// Builds the "SubnetIds" `create_stack` `Parameter` from a per-AZ node
+/// placement `Assignment` (see `aws::placement::assign`/`rebalance`) and
+/// each AZ's subnet ID: one subnet ID per node, ordered by node index, so
+/// the ASG launches node N into whichever AZ the placement algorithm
+/// assigned it. This is the integration point `aws::placement::Assignment`
+/// refers to as "the stack builder feeds the resulting per-AZ node indices
+/// into the subnet-id Parameter list".
+pub fn subnet_ids_parameter(
+    assignment: &Assignment,
+    az_subnet_ids: &BTreeMap<String, String>,
+) -> io::Result<Parameter> {
+    let total_nodes: usize = assignment.values().map(|v| v.len()).sum();
+    let mut subnet_ids: Vec<Option<String>> = vec![None; total_nodes];
+
+    for (az, node_indices) in assignment {
+        let subnet_id = az_subnet_ids.get(az).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("no subnet id configured for AZ {}", az),
+            )
+        })?;
+        for &idx in node_indices {
+            let slot = subnet_ids.get_mut(idx).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("node index {} out of range for {} total nodes", idx, total_nodes),
+                )
+            })?;
+            *slot = Some(subnet_id.clone());
+        }
+    }
+
+    let subnet_ids: Vec<String> = subnet_ids
+        .into_iter()
+        .enumerate()
+        .map(|(idx, subnet_id)| {
+            subnet_id.ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("node index {} is not present in the assignment", idx),
+                )
+            })
+        })
+        .collect::<io::Result<_>>()?;
+
+    Ok(Parameter::builder()
+        .parameter_key("SubnetIds")
+        .parameter_value(subnet_ids.join(","))
+        .build())
+}
+
+#[test]
+fn test_subnet_ids_parameter() {
+    let assignment = Assignment::from([
+        ("us-west-2a".to_string(), vec![0, 2]),
+        ("us-west-2b".to_string(), vec![1, 3]),
+    ]);
+    let az_subnet_ids = BTreeMap::from([
+        ("us-west-2a".to_string(), "subnet-aaa".to_string()),
+        ("us-west-2b".to_string(), "subnet-bbb".to_string()),
+    ]);
+
+    let param = subnet_ids_parameter(&assignment, &az_subnet_ids).unwrap();
+    assert_eq!(param.parameter_value.unwrap(), "subnet-aaa,subnet-bbb,subnet-aaa,subnet-bbb");
+
+    let missing_az = Assignment::from([("us-west-2c".to_string(), vec![0])]);
+    assert!(subnet_ids_parameter(&missing_az, &az_subnet_ids).is_err());
+}
@@ -0,0 +1,170 @@
+use std::{
+    collections::HashSet,
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use chrono::{DateTime, Utc};
+use log::{info, warn};
+use tokio::time::sleep;
+
+use crate::avalanche::avalanchego::api::health;
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Condition a cluster-wide poll waits for before returning successfully.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ClusterCondition {
+    /// Every endpoint must report healthy.
+    AllHealthy,
+    /// More than half of the endpoints must report healthy.
+    QuorumHealthy,
+}
+
+impl ClusterCondition {
+    fn is_satisfied(&self, total: usize, healthy: usize) -> bool {
+        if total == 0 {
+            return false;
+        }
+        match self {
+            ClusterCondition::AllHealthy => healthy == total,
+            ClusterCondition::QuorumHealthy => healthy * 2 > total,
+        }
+    }
+}
+
+/// Latest observed state for a single node endpoint being polled.
+#[derive(Debug, Clone)]
+pub struct NodeState {
+    pub endpoint: String,
+    pub healthy: bool,
+    pub last_error: Option<String>,
+    /// Taken from the node's own "bootstrapped" health check result.
+    pub contiguous_failures: i64,
+    /// Taken from the node's own "bootstrapped" health check result.
+    pub time_of_first_failure: Option<DateTime<Utc>>,
+}
+
+/// Structured summary of polling a whole fleet of node endpoints.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterHealth {
+    pub healthy: HashSet<String>,
+    pub unhealthy: Vec<NodeState>,
+    /// Whether the requested "ClusterCondition" was met before the deadline.
+    pub condition_met: bool,
+}
+
+/// Polls every given endpoint concurrently -- one task per endpoint -- using
+/// exponential backoff between rounds. Each task stops once its endpoint
+/// reports healthy or `deadline` elapses, and this returns only after every
+/// task has stopped; `condition` is checked once against the final states
+/// to set `condition_met`. Polling does not stop early when `condition`
+/// already holds, so a `QuorumHealthy` poll with a node that never becomes
+/// healthy runs for the full `deadline`.
+///
+/// Per-node `contiguous_failures` and `time_of_first_failure` are read
+/// straight off the "bootstrapped" entry already modeled in
+/// `health::CheckResult`, so callers can distinguish a node that just
+/// started failing from one that has been down for minutes, without this
+/// aggregator having to track failure history itself.
+pub async fn poll_cluster(
+    endpoints: Vec<String>,
+    condition: ClusterCondition,
+    deadline: Duration,
+) -> io::Result<ClusterHealth> {
+    if endpoints.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "no endpoints to poll"));
+    }
+
+    let started = Instant::now();
+    let tasks: Vec<_> = endpoints
+        .into_iter()
+        .map(|ep| tokio::spawn(poll_single(ep, deadline)))
+        .collect();
+
+    let mut result = ClusterHealth::default();
+    for t in tasks {
+        let state = t
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("poll task panicked: {}", e)))?;
+        if state.healthy {
+            result.healthy.insert(state.endpoint.clone());
+        } else {
+            result.unhealthy.push(state);
+        }
+    }
+
+    let total = result.healthy.len() + result.unhealthy.len();
+    result.condition_met = condition.is_satisfied(total, result.healthy.len());
+    if !result.condition_met {
+        warn!(
+            "cluster health condition {:?} not met after {:?} ({}/{} healthy)",
+            condition,
+            started.elapsed(),
+            result.healthy.len(),
+            total
+        );
+    }
+    Ok(result)
+}
+
+/// Polls a single endpoint with exponential backoff until it reports
+/// healthy or `deadline` elapses, returning its last observed state.
+async fn poll_single(endpoint: String, deadline: Duration) -> NodeState {
+    let started = Instant::now();
+    let ep_arc = Arc::new(endpoint.clone());
+    let mut backoff = INITIAL_BACKOFF;
+
+    let mut state = NodeState {
+        endpoint: endpoint.clone(),
+        healthy: false,
+        last_error: None,
+        contiguous_failures: 0,
+        time_of_first_failure: None,
+    };
+
+    loop {
+        match health::check(Arc::clone(&ep_arc), false).await {
+            Ok(resp) => {
+                state.healthy = resp.healthy.unwrap_or(false);
+                state.last_error = None;
+                if let Some(checks) = &resp.checks {
+                    if let Some(bootstrapped) = checks.get("bootstrapped") {
+                        state.contiguous_failures = bootstrapped.contiguous_failures.unwrap_or(0);
+                        state.time_of_first_failure = bootstrapped.time_of_first_failure;
+                    }
+                }
+                if state.healthy {
+                    info!("{} is healthy", endpoint);
+                    return state;
+                }
+            }
+            Err(e) => {
+                info!("{} health check failed: {}", endpoint, e);
+                state.healthy = false;
+                state.last_error = Some(e.to_string());
+            }
+        }
+
+        let elapsed = started.elapsed();
+        if elapsed >= deadline {
+            return state;
+        }
+
+        let sleep_for = backoff.min(deadline - elapsed);
+        sleep(sleep_for).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+#[test]
+fn test_cluster_condition() {
+    assert!(ClusterCondition::AllHealthy.is_satisfied(3, 3));
+    assert!(!ClusterCondition::AllHealthy.is_satisfied(3, 2));
+
+    assert!(ClusterCondition::QuorumHealthy.is_satisfied(3, 2));
+    assert!(!ClusterCondition::QuorumHealthy.is_satisfied(4, 2));
+    assert!(!ClusterCondition::QuorumHealthy.is_satisfied(0, 0));
+}
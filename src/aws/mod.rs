@@ -0,0 +1,90 @@
+use std::io;
+
+use aws_config::meta::region::RegionProviderChain;
+use aws_types::SdkConfig;
+use serde::{Deserialize, Serialize};
+
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod cloudformation;
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod health;
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod placement;
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod s3;
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod watch;
+
+/// Represents the AWS resources used for a single cluster/test.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Resources {
+    #[serde(default)]
+    pub region: String,
+    #[serde(default)]
+    pub s3_bucket: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_backup_s3_region: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_backup_s3_bucket: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub db_backup_s3_key: Option<String>,
+
+    /// Non-AWS, S3-compatible endpoint (e.g. a MinIO/Garage/Wasabi URL) to
+    /// upload/download "s3_bucket" and the DB backup through, instead of
+    /// the regional AWS S3 hostname. "None" uses native AWS S3.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3_endpoint: Option<String>,
+    /// Region to sign S3 requests with when "s3_endpoint" is set. Most
+    /// S3-compatible servers still require a region for SigV4 signing even
+    /// though it no longer selects a hostname, so this MUST be set whenever
+    /// "s3_endpoint" is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3_region: Option<String>,
+    /// Whether to address the bucket as "{endpoint}/{bucket}" (path-style)
+    /// rather than "{bucket}.{endpoint}" (virtual-hosted-style). Most
+    /// self-hosted S3-compatible servers require path-style access.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3_path_style_access: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nlb_acm_certificate_arn: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_system_logs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance_system_metrics: Option<bool>,
+}
+
+/// Loads the shared AWS SDK config, defaulting the region provider chain to
+/// the environment/profile when `region` is not given.
+pub async fn load_config(region: Option<String>) -> io::Result<SdkConfig> {
+    let region_provider =
+        RegionProviderChain::first_try(region.map(aws_types::region::Region::new))
+            .or_default_provider();
+    Ok(aws_config::from_env().region(region_provider).load().await)
+}
+
+/// Builds an S3 client against `resources.s3_endpoint` when set, so
+/// `resources.s3_bucket` (and the DB backup bucket) can live on any
+/// S3-compatible server (MinIO, Garage, Wasabi) rather than native AWS S3.
+/// Falls back to a plain AWS S3 client built from `shared_config` when no
+/// custom endpoint is configured.
+pub fn s3_client(shared_config: &SdkConfig, resources: &Resources) -> aws_sdk_s3::Client {
+    match &resources.s3_endpoint {
+        Some(endpoint) => {
+            let region = resources
+                .s3_region
+                .clone()
+                .unwrap_or_else(|| resources.region.clone());
+            let s3_config = aws_sdk_s3::config::Builder::from(shared_config)
+                .endpoint_url(endpoint.clone())
+                .region(aws_types::region::Region::new(region))
+                .force_path_style(resources.s3_path_style_access.unwrap_or(false))
+                .build();
+            aws_sdk_s3::Client::from_conf(s3_config)
+        }
+        None => aws_sdk_s3::Client::new(shared_config),
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+};
+
+/// Assignment of node indices (stable across resizes) to an availability
+/// zone id. The stack builder feeds the resulting per-AZ node indices into
+/// the subnet-id `Parameter` list passed to `cloudformation::Manager`'s
+/// `create_stack`.
+pub type Assignment = BTreeMap<String, Vec<usize>>;
+
+/// Computes the target node count for each AZ given the desired fleet size
+/// and the current per-AZ node counts (all zero for a first-time
+/// assignment). Targets are `floor(n/k)` for every AZ, with the remainder
+/// `n % k` distributed one-by-one to the AZs currently holding the fewest
+/// nodes, ties broken by AZ id so the result is deterministic.
+fn target_counts(n: usize, current_counts: &BTreeMap<String, usize>) -> BTreeMap<String, usize> {
+    let k = current_counts.len();
+    if k == 0 {
+        return BTreeMap::new();
+    }
+    let base = n / k;
+    let remainder = n % k;
+
+    let mut azs: Vec<&String> = current_counts.keys().collect();
+    azs.sort_by(|a, b| {
+        current_counts[*a]
+            .cmp(&current_counts[*b])
+            .then_with(|| a.cmp(b))
+    });
+
+    let mut targets: BTreeMap<String, usize> =
+        current_counts.keys().map(|az| (az.clone(), base)).collect();
+    for az in azs.into_iter().take(remainder) {
+        *targets.get_mut(az).expect("az present in current_counts") += 1;
+    }
+    targets
+}
+
+/// Computes a fresh AZ -> node-index assignment for `node_count` nodes
+/// spread as evenly as possible across `az_capacities`.
+pub fn assign(node_count: usize, az_capacities: &BTreeMap<String, usize>) -> io::Result<Assignment> {
+    rebalance(&Assignment::new(), node_count, az_capacities)
+}
+
+/// Recomputes an AZ -> node-index assignment for a (possibly resized) fleet
+/// of `node_count` nodes, starting from `existing` and moving only the
+/// minimum number of nodes needed to reach the new balanced targets -- e.g.
+/// scaling 2 AZs to 3 rebalances roughly a third of nodes instead of
+/// reshuffling the whole fleet from scratch.
+pub fn rebalance(
+    existing: &Assignment,
+    node_count: usize,
+    az_capacities: &BTreeMap<String, usize>,
+) -> io::Result<Assignment> {
+    if az_capacities.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "no AZs to place nodes into",
+        ));
+    }
+    let total_capacity: usize = az_capacities.values().sum();
+    if total_capacity < node_count {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "total AZ capacity {} is less than the requested node count {}",
+                total_capacity, node_count
+            ),
+        ));
+    }
+
+    let mut current_counts: BTreeMap<String, usize> =
+        az_capacities.keys().map(|az| (az.clone(), 0)).collect();
+    for (az, nodes) in existing {
+        if let Some(c) = current_counts.get_mut(az) {
+            *c = nodes.len();
+        }
+    }
+
+    let targets = target_counts(node_count, &current_counts);
+    for (az, target) in &targets {
+        let cap = *az_capacities.get(az).unwrap_or(&0);
+        if *target > cap {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("AZ {} target {} exceeds its capacity {}", az, target, cap),
+            ));
+        }
+    }
+
+    // keep nodes already assigned to an AZ that is still at/under target;
+    // everything else (overflow, and brand-new node indices from a fleet
+    // resize) goes into a shared pool to be redistributed.
+    let mut result: Assignment = BTreeMap::new();
+    let mut pool: Vec<usize> = Vec::new();
+    let mut placed = vec![false; node_count];
+
+    for (az, target) in &targets {
+        let mut nodes = existing.get(az).cloned().unwrap_or_default();
+        nodes.retain(|idx| *idx < node_count);
+        nodes.sort_unstable();
+        while nodes.len() > *target {
+            pool.push(nodes.pop().expect("nodes non-empty while over target"));
+        }
+        for idx in &nodes {
+            placed[*idx] = true;
+        }
+        result.insert(az.clone(), nodes);
+    }
+    for (idx, is_placed) in placed.iter().enumerate() {
+        if !is_placed && !pool.contains(&idx) {
+            pool.push(idx);
+        }
+    }
+    pool.sort_unstable();
+
+    let mut az_ids: Vec<&String> = targets.keys().collect();
+    az_ids.sort();
+    let mut pool_iter = pool.into_iter();
+    for az in az_ids {
+        let target = targets[az];
+        let nodes = result.entry(az.clone()).or_default();
+        while nodes.len() < target {
+            nodes.push(
+                pool_iter
+                    .next()
+                    .expect("pool exhausted before all targets were met"),
+            );
+        }
+        nodes.sort_unstable();
+    }
+
+    Ok(result)
+}
+
+/// The maximum number of nodes any single AZ can hold when `node_count`
+/// nodes are spread across `az_count` AZs -- the worst-case fleet impact of
+/// losing one AZ. Equal to `ceil(node_count / az_count)`.
+pub fn max_nodes_per_az(node_count: usize, az_count: usize) -> usize {
+    if az_count == 0 {
+        return node_count;
+    }
+    (node_count + az_count - 1) / az_count
+}
+
+#[test]
+fn test_assign_evenly() {
+    let caps = BTreeMap::from([
+        (String::from("us-west-2a"), 10),
+        (String::from("us-west-2b"), 10),
+        (String::from("us-west-2c"), 10),
+    ]);
+    let a = assign(10, &caps).unwrap();
+    let mut counts: Vec<usize> = a.values().map(|v| v.len()).collect();
+    counts.sort_unstable();
+    assert_eq!(counts, vec![3, 3, 4]);
+    // remainder goes to the lowest-id AZ when all counts start at zero.
+    assert_eq!(a[&String::from("us-west-2a")].len(), 4);
+
+    assert_eq!(max_nodes_per_az(10, 3), 4);
+}
+
+#[test]
+fn test_rebalance_moves_minimum_nodes() {
+    let caps2 = BTreeMap::from([
+        (String::from("us-west-2a"), 10),
+        (String::from("us-west-2b"), 10),
+    ]);
+    let a = assign(6, &caps2).unwrap();
+    assert_eq!(a[&String::from("us-west-2a")].len(), 3);
+    assert_eq!(a[&String::from("us-west-2b")].len(), 3);
+
+    let caps3 = BTreeMap::from([
+        (String::from("us-west-2a"), 10),
+        (String::from("us-west-2b"), 10),
+        (String::from("us-west-2c"), 10),
+    ]);
+    let b = rebalance(&a, 6, &caps3).unwrap();
+    let mut counts: Vec<usize> = b.values().map(|v| v.len()).collect();
+    counts.sort_unstable();
+    assert_eq!(counts, vec![2, 2, 2]);
+
+    // at most one third of the fleet (2 of 6 nodes) should have moved out
+    // of its original AZ.
+    let moved = a
+        .iter()
+        .flat_map(|(az, nodes)| nodes.iter().map(move |n| (az.clone(), *n)))
+        .filter(|(az, n)| !b.get(az).map(|v| v.contains(n)).unwrap_or(false))
+        .count();
+    assert!(moved <= 2, "expected <=2 nodes moved, got {}", moved);
+}
@@ -0,0 +1,84 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    path::Path,
+};
+
+use async_compression::tokio::bufread::GzipDecoder;
+use aws_sdk_s3::Client;
+use futures::stream::StreamExt;
+use log::info;
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncWriteExt, BufReader},
+};
+use tokio_util::io::StreamReader;
+
+const PROGRESS_LOG_EVERY_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Streams a gzip-compressed DB backup object directly from S3 and
+/// decompresses it on the fly into `target_dir/file_name`, rather than
+/// buffering the whole (potentially many-GB) object in memory.
+///
+/// The pipeline is `GetObject` byte stream -> async `GzipDecoder` (bufread)
+/// -> chunked write to disk, so a freshly provisioned node can bootstrap
+/// from an existing chain-state snapshot without a manual
+/// `aws s3 cp` + `gunzip` round-trip. Returns the number of decompressed
+/// bytes written.
+pub async fn restore_db_backup(
+    cli: &Client,
+    s3_bucket: &str,
+    s3_key: &str,
+    target_dir: &str,
+    file_name: &str,
+) -> io::Result<u64> {
+    info!(
+        "restoring DB backup from s3://{}/{} into {}/{}",
+        s3_bucket, s3_key, target_dir, file_name
+    );
+
+    tokio::fs::create_dir_all(target_dir).await?;
+    let target_path = Path::new(target_dir).join(file_name);
+
+    let resp = cli
+        .get_object()
+        .bucket(s3_bucket)
+        .key(s3_key)
+        .send()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed GetObject s3://{}/{} ({})", s3_bucket, s3_key, e),
+            )
+        })?;
+
+    let byte_stream = resp
+        .body
+        .map(|chunk| chunk.map_err(|e| io::Error::new(ErrorKind::Other, e)));
+    let stream_reader = StreamReader::new(byte_stream);
+    let mut decoder = GzipDecoder::new(BufReader::new(stream_reader));
+
+    let mut out = File::create(&target_path).await?;
+    let mut buf = vec![0u8; 64 * 1024];
+    let mut total: u64 = 0;
+    let mut last_logged: u64 = 0;
+    loop {
+        let n = decoder.read(&mut buf).await?;
+        if n == 0 {
+            break;
+        }
+        out.write_all(&buf[..n]).await?;
+        total += n as u64;
+        if total - last_logged >= PROGRESS_LOG_EVERY_BYTES {
+            info!("restored {} bytes so far -> {:?}", total, target_path);
+            last_logged = total;
+        }
+    }
+    out.flush().await?;
+
+    info!(
+        "restored {} bytes total from s3://{}/{} to {:?}",
+        total, s3_bucket, s3_key, target_path
+    );
+    Ok(total)
+}
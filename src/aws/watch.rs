@@ -0,0 +1,156 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+    time::{Duration, Instant},
+};
+
+use aws_sdk_s3::Client as S3Client;
+use log::info;
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::{node, StorageNamespace};
+
+/// Opaque snapshot of a discovery prefix's listing -- a "(key,
+/// last_modified, etag)" tuple per object -- used by "watch_prefix" to
+/// detect any create/modify/delete since the caller last observed it.
+/// Callers should treat this as opaque and only ever pass back a token
+/// previously returned by "watch_prefix".
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct WatchToken(BTreeMap<String, ObjectStamp>);
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ObjectStamp {
+    last_modified: String,
+    etag: String,
+}
+
+const REPOLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Long-polls "namespace_prefix" until any key under it is created,
+/// modified, or deleted relative to "since_token", or "timeout" elapses,
+/// re-listing the prefix every "REPOLL_INTERVAL" in between instead of
+/// returning immediately on every call. This lets a caller (e.g.
+/// "avalanched" waiting for anchor nodes to move from bootstrapping to
+/// ready) block efficiently on a state transition instead of busy-listing
+/// S3 on a fixed interval.
+///
+/// Returns the "node::Node" entries (decoded via
+/// "StorageNamespace::parse_node_from_path") for every key that changed,
+/// plus the new token snapshot to pass into the next call. An unchanged
+/// prefix after "timeout" returns an empty "Vec" and the same token.
+pub async fn watch_prefix(
+    s3_cli: &S3Client,
+    s3_bucket: &str,
+    namespace_prefix: &str,
+    since_token: &WatchToken,
+    timeout: Duration,
+) -> io::Result<(Vec<node::Node>, WatchToken)> {
+    let started = Instant::now();
+    loop {
+        let current = snapshot(s3_cli, s3_bucket, namespace_prefix).await?;
+        let changed_keys = diff(since_token, &current);
+
+        if !changed_keys.is_empty() || started.elapsed() >= timeout {
+            let mut changed_nodes = Vec::new();
+            for key in &changed_keys {
+                match StorageNamespace::parse_node_from_path(key) {
+                    Ok(node) => changed_nodes.push(node),
+                    Err(e) => info!("skipping undecodable discovery key {} ({})", key, e),
+                }
+            }
+            return Ok((changed_nodes, current));
+        }
+
+        let remaining = timeout.saturating_sub(started.elapsed());
+        sleep(REPOLL_INTERVAL.min(remaining)).await;
+    }
+}
+
+/// Keys present in exactly one of "old"/"new", or present in both with a
+/// different "last_modified"/"etag".
+fn diff(old: &WatchToken, new: &WatchToken) -> Vec<String> {
+    let mut changed = Vec::new();
+    for (key, stamp) in &new.0 {
+        match old.0.get(key) {
+            Some(old_stamp) if old_stamp == stamp => {}
+            _ => changed.push(key.clone()),
+        }
+    }
+    for key in old.0.keys() {
+        if !new.0.contains_key(key) {
+            changed.push(key.clone());
+        }
+    }
+    changed
+}
+
+#[test]
+fn test_diff() {
+    let unchanged = ObjectStamp {
+        last_modified: "t0".to_string(),
+        etag: "etag-a".to_string(),
+    };
+    let modified_old = ObjectStamp {
+        last_modified: "t0".to_string(),
+        etag: "etag-b".to_string(),
+    };
+    let modified_new = ObjectStamp {
+        last_modified: "t1".to_string(),
+        etag: "etag-c".to_string(),
+    };
+    let created = ObjectStamp {
+        last_modified: "t0".to_string(),
+        etag: "etag-d".to_string(),
+    };
+    let deleted = ObjectStamp {
+        last_modified: "t0".to_string(),
+        etag: "etag-e".to_string(),
+    };
+
+    let old = WatchToken(BTreeMap::from([
+        ("unchanged".to_string(), unchanged.clone()),
+        ("modified".to_string(), modified_old),
+        ("deleted".to_string(), deleted),
+    ]));
+    let new = WatchToken(BTreeMap::from([
+        ("unchanged".to_string(), unchanged),
+        ("modified".to_string(), modified_new),
+        ("created".to_string(), created),
+    ]));
+
+    let mut changed = diff(&old, &new);
+    changed.sort();
+    assert_eq!(changed, vec!["created", "deleted", "modified"]);
+}
+
+async fn snapshot(s3_cli: &S3Client, s3_bucket: &str, prefix: &str) -> io::Result<WatchToken> {
+    let mut entries = BTreeMap::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let mut req = s3_cli.list_objects_v2().bucket(s3_bucket).prefix(prefix);
+        if let Some(token) = &continuation_token {
+            req = req.continuation_token(token);
+        }
+        let resp = req.send().await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to list s3://{}/{} ({})", s3_bucket, prefix, e),
+            )
+        })?;
+
+        for obj in resp.contents.unwrap_or_default() {
+            if let (Some(key), Some(etag)) = (obj.key, obj.e_tag) {
+                let last_modified = obj.last_modified.map(|d| d.to_string()).unwrap_or_default();
+                entries.insert(key, ObjectStamp { last_modified, etag });
+            }
+        }
+
+        if resp.is_truncated {
+            continuation_token = resp.next_continuation_token;
+        } else {
+            break;
+        }
+    }
+    Ok(WatchToken(entries))
+}
@@ -0,0 +1,240 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    time::{Duration, Instant},
+};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tokio::time::sleep;
+
+use crate::{key, utils::http};
+
+/// Opt-in post-bootstrap load/benchmark smoke test: sends transactions
+/// against the C-chain RPC endpoint for "duration_secs" using the
+/// cluster's pre-funded "generated_seed_private_keys", so a freshly
+/// provisioned fleet reports throughput and latency before being handed to
+/// the caller instead of just coming up idle. Transactions are sent one at
+/// a time, each waiting for its receipt and then a 1/"tx_rate" second
+/// pause, so "tx_rate" is an upper bound on the achieved rate. No load is
+/// sent to the X-chain.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct BenchmarkSpec {
+    pub tx_rate: u32,
+    pub duration_secs: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<BenchmarkResult>,
+}
+
+/// Measured outcome of a "run" call, written back onto "Spec.benchmark".
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct BenchmarkResult {
+    pub confirmed_txs: u64,
+    pub failed_txs: u64,
+    pub throughput_tps: f64,
+    pub latency_ms_p50: f64,
+    pub latency_ms_p90: f64,
+    pub latency_ms_p99: f64,
+}
+
+#[derive(Debug, Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'a str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<serde_json::Value>,
+}
+
+const RPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs the benchmark against "rpc_c_chain", cycling through "seed_keys" so
+/// load is spread across all pre-funded accounts instead of a single nonce
+/// stream. "rpc_x_chain" is only logged; no transactions are sent to it.
+pub async fn run(
+    rpc_c_chain: &str,
+    rpc_x_chain: &str,
+    seed_keys: &[key::PrivateKeyInfo],
+    tx_rate: u32,
+    duration_secs: u64,
+) -> io::Result<BenchmarkResult> {
+    if seed_keys.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "cannot run benchmark without any generated_seed_private_keys",
+        ));
+    }
+    if tx_rate == 0 {
+        return Err(Error::new(ErrorKind::InvalidInput, "'tx_rate' must be >0"));
+    }
+
+    info!(
+        "running benchmark against c-chain {} (x-chain '{}') at {} tx/s for {}s",
+        rpc_c_chain, rpc_x_chain, tx_rate, duration_secs
+    );
+
+    let interval = Duration::from_secs_f64(1.0 / tx_rate as f64);
+    let deadline = Instant::now() + Duration::from_secs(duration_secs);
+
+    // one local nonce counter per seed key, seeded from the chain's own
+    // count, so cycling through "seed_keys" under any tx_rate/duration
+    // never re-signs a later transaction with an already-used nonce
+    let mut nonces = Vec::with_capacity(seed_keys.len());
+    for key_info in seed_keys {
+        nonces.push(fetch_nonce(rpc_c_chain, &key_info.eth_address).await?);
+    }
+
+    let mut confirmed_txs: u64 = 0;
+    let mut failed_txs: u64 = 0;
+    let mut latencies_ms: Vec<f64> = Vec::new();
+    let mut key_idx = 0usize;
+
+    while Instant::now() < deadline {
+        let i = key_idx % seed_keys.len();
+        let key_info = &seed_keys[i];
+        key_idx += 1;
+
+        let started = Instant::now();
+        match send_and_confirm_c_chain_tx(rpc_c_chain, key_info, &mut nonces[i]).await {
+            Ok(()) => {
+                confirmed_txs += 1;
+                latencies_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+            }
+            Err(e) => {
+                warn!("benchmark tx failed ({})", e);
+                failed_txs += 1;
+            }
+        }
+
+        sleep(interval).await;
+    }
+
+    latencies_ms.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let elapsed_secs = duration_secs.max(1) as f64;
+
+    Ok(BenchmarkResult {
+        confirmed_txs,
+        failed_txs,
+        throughput_tps: confirmed_txs as f64 / elapsed_secs,
+        latency_ms_p50: percentile(&latencies_ms, 0.50),
+        latency_ms_p90: percentile(&latencies_ms, 0.90),
+        latency_ms_p99: percentile(&latencies_ms, 0.99),
+    })
+}
+
+/// Builds, signs (via "key::PrivateKeyInfo::sign_raw_transaction"), sends,
+/// and polls a single no-op self-transfer on the C-chain until confirmed.
+/// "nonce" is this key's next local nonce; it's advanced as soon as the
+/// node accepts the raw transaction, regardless of whether the receipt
+/// below ever confirms, since the nonce slot is consumed the moment the
+/// node accepts it into its mempool.
+async fn send_and_confirm_c_chain_tx(
+    rpc_c_chain: &str,
+    key_info: &key::PrivateKeyInfo,
+    nonce: &mut u64,
+) -> io::Result<()> {
+    let raw_tx = key_info
+        .sign_raw_transaction(&key_info.eth_address, *nonce)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to sign tx ({})", e)))?;
+
+    let send_req = JsonRpcRequest {
+        jsonrpc: "2.0",
+        id: 1,
+        method: "eth_sendRawTransaction",
+        params: serde_json::json!([raw_tx]),
+    };
+    let resp = call(rpc_c_chain, &send_req).await?;
+    let tx_hash = resp
+        .result
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .ok_or_else(|| Error::new(ErrorKind::Other, "eth_sendRawTransaction returned no hash"))?;
+    *nonce += 1;
+
+    // poll for the receipt; a real deployment's block time bounds this loop
+    for _ in 0..50 {
+        let receipt_req = JsonRpcRequest {
+            jsonrpc: "2.0",
+            id: 2,
+            method: "eth_getTransactionReceipt",
+            params: serde_json::json!([tx_hash]),
+        };
+        let resp = call(rpc_c_chain, &receipt_req).await?;
+        if resp.result.map(|v| !v.is_null()).unwrap_or(false) {
+            return Ok(());
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    Err(Error::new(
+        ErrorKind::TimedOut,
+        format!("tx {} was not confirmed in time", tx_hash),
+    ))
+}
+
+/// Fetches "address"'s current (pending-inclusive) transaction count to
+/// seed its local nonce counter in "run".
+async fn fetch_nonce(rpc_c_chain: &str, address: &str) -> io::Result<u64> {
+    let req = JsonRpcRequest {
+        jsonrpc: "2.0",
+        id: 0,
+        method: "eth_getTransactionCount",
+        params: serde_json::json!([address, "pending"]),
+    };
+    let resp = call(rpc_c_chain, &req).await?;
+    let hex_nonce = resp
+        .result
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+        .ok_or_else(|| Error::new(ErrorKind::Other, "eth_getTransactionCount returned no value"))?;
+    u64::from_str_radix(hex_nonce.trim_start_matches("0x"), 16).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("invalid eth_getTransactionCount result '{}' ({})", hex_nonce, e),
+        )
+    })
+}
+
+async fn call(rpc_url: &str, req: &JsonRpcRequest<'_>) -> io::Result<JsonRpcResponse> {
+    let https = rpc_url.starts_with("https");
+    let http_req = http::create_post_json(rpc_url, "", req)?;
+    // unlike the avalanchego health endpoint (a freshly bootstrapped
+    // node's self-signed HTTPS cert), the RPC endpoint under benchmark is
+    // expected to sit behind a real, verified certificate
+    let buf = http::read_bytes(http_req, RPC_TIMEOUT, https, false).await?;
+    let resp: JsonRpcResponse = serde_json::from_slice(&buf).map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("invalid JSON-RPC response ({})", e),
+        )
+    })?;
+    if let Some(err) = &resp.error {
+        return Err(Error::new(ErrorKind::Other, format!("JSON-RPC error: {}", err)));
+    }
+    Ok(resp)
+}
+
+fn percentile(sorted_ms: &[f64], p: f64) -> f64 {
+    if sorted_ms.is_empty() {
+        return 0.0;
+    }
+    let idx = ((sorted_ms.len() as f64 - 1.0) * p).round() as usize;
+    sorted_ms[idx]
+}
+
+#[test]
+fn test_percentile() {
+    assert_eq!(percentile(&[], 0.50), 0.0);
+
+    let sorted = vec![10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0];
+    assert_eq!(percentile(&sorted, 0.0), 10.0);
+    assert_eq!(percentile(&sorted, 1.0), 100.0);
+    assert_eq!(percentile(&sorted, 0.50), 60.0);
+    assert_eq!(percentile(&sorted, 0.90), 90.0);
+}
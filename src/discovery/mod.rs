@@ -0,0 +1,273 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, Error, ErrorKind},
+    time::Duration,
+};
+
+use k8s_openapi::{
+    api::{
+        apps::v1::{StatefulSet, StatefulSetSpec},
+        core::v1::{Container, ContainerPort, Pod, PodSpec, PodTemplateSpec, Service, ServicePort, ServiceSpec},
+    },
+    apimachinery::pkg::{apis::meta::v1::{LabelSelector, ObjectMeta}, util::intstr::IntOrString},
+};
+use kube::{
+    api::{Api, DeleteParams, ListParams, PostParams},
+    Client,
+};
+use log::info;
+use tokio::time::sleep;
+
+/// A single discovered node, analogous to `node::Node` but sourced from a
+/// pod rather than an EC2 instance/ASG discovery file.
+#[derive(Debug, Clone)]
+pub struct DiscoveredNode {
+    pub pod_name: String,
+    pub pod_ip: Option<String>,
+    pub http_endpoint: Option<String>,
+}
+
+/// Kubernetes-backed discovery manager. Provides the same logical
+/// operations `cloudformation::Manager` exposes for EC2 -- bring up N
+/// nodes, poll until ready, resolve their endpoints, tear down -- but
+/// implemented as a `StatefulSet` fronted by a headless `Service` per node
+/// group instead of an ASG, so users without an AWS account can still
+/// deploy a fleet. A `StatefulSet`'s pods get stable, predictable names
+/// ("{prefix}-0".."{prefix}-(count-1)"), so unlike the old bare-`Pod`
+/// approach, a restarted process can always recompute `pod_names` without
+/// needing `list_nodes` just to find them -- `list_nodes` remains for
+/// discovering a fleet whose `prefix`/`count` isn't already known. Once
+/// resolved, endpoints flow unchanged into `aws::health`.
+pub struct Manager {
+    client: Client,
+    namespace: String,
+}
+
+impl Manager {
+    pub fn new(client: Client, namespace: &str) -> Self {
+        Self {
+            client,
+            namespace: namespace.to_string(),
+        }
+    }
+
+    /// Creates a headless `Service` named `prefix` (for stable per-pod DNS)
+    /// and a `StatefulSet` named `prefix` with `count` replicas running
+    /// `image`, each exposing `http_port`, labeled with `labels` for
+    /// discovery by label selector via `list_nodes`. Returns the
+    /// deterministic pod names ("{prefix}-0".."{prefix}-(count-1)").
+    pub async fn create_nodes(
+        &self,
+        prefix: &str,
+        count: u32,
+        image: &str,
+        http_port: i32,
+        labels: BTreeMap<String, String>,
+    ) -> io::Result<Vec<String>> {
+        let svc_api: Api<Service> = Api::namespaced(self.client.clone(), &self.namespace);
+        let service = Service {
+            metadata: ObjectMeta {
+                name: Some(prefix.to_string()),
+                namespace: Some(self.namespace.clone()),
+                labels: Some(labels.clone()),
+                ..Default::default()
+            },
+            spec: Some(ServiceSpec {
+                cluster_ip: Some("None".to_string()),
+                selector: Some(labels.clone()),
+                ports: Some(vec![ServicePort {
+                    port: http_port,
+                    target_port: Some(IntOrString::Int(http_port)),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        info!(
+            "creating headless service {} in namespace {}",
+            prefix, self.namespace
+        );
+        svc_api.create(&PostParams::default(), &service).await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to create service {} ({})", prefix, e),
+            )
+        })?;
+
+        let sts_api: Api<StatefulSet> = Api::namespaced(self.client.clone(), &self.namespace);
+        let statefulset = StatefulSet {
+            metadata: ObjectMeta {
+                name: Some(prefix.to_string()),
+                namespace: Some(self.namespace.clone()),
+                labels: Some(labels.clone()),
+                ..Default::default()
+            },
+            spec: Some(StatefulSetSpec {
+                replicas: Some(count as i32),
+                service_name: prefix.to_string(),
+                selector: LabelSelector {
+                    match_labels: Some(labels.clone()),
+                    ..Default::default()
+                },
+                template: PodTemplateSpec {
+                    metadata: Some(ObjectMeta {
+                        labels: Some(labels.clone()),
+                        ..Default::default()
+                    }),
+                    spec: Some(PodSpec {
+                        containers: vec![Container {
+                            name: prefix.to_string(),
+                            image: Some(image.to_string()),
+                            ports: Some(vec![ContainerPort {
+                                container_port: http_port,
+                                ..Default::default()
+                            }]),
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }),
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        info!(
+            "creating statefulset {} ({} replicas) in namespace {}",
+            prefix, count, self.namespace
+        );
+        sts_api.create(&PostParams::default(), &statefulset).await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to create statefulset {} ({})", prefix, e),
+            )
+        })?;
+
+        Ok((0..count).map(|i| format!("{}-{}", prefix, i)).collect())
+    }
+
+    /// Lists the names of every pod in `namespace` carrying all of
+    /// `labels`, so a process that restarted (and so lost the `pod_names`
+    /// returned by `create_nodes`) can rediscover its fleet from the
+    /// labels attached at creation instead of re-listing EC2-style state
+    /// that, unlike `StorageNamespace::Discover*` on the AWS backend, has
+    /// no other persisted home.
+    pub async fn list_nodes(&self, labels: &BTreeMap<String, String>) -> io::Result<Vec<String>> {
+        let api: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let selector = labels
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<String>>()
+            .join(",");
+
+        let pods = api
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!(
+                        "failed to list pods in namespace {} with labels '{}' ({})",
+                        self.namespace, selector, e
+                    ),
+                )
+            })?;
+
+        Ok(pods
+            .items
+            .into_iter()
+            .filter_map(|pod| pod.metadata.name)
+            .collect())
+    }
+
+    /// Polls the given pods until every one reports a "Running" phase and a
+    /// pod IP, or `deadline` elapses.
+    pub async fn poll_ready(
+        &self,
+        pod_names: &[String],
+        http_port: i32,
+        deadline: Duration,
+    ) -> io::Result<Vec<DiscoveredNode>> {
+        let api: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let started = tokio::time::Instant::now();
+
+        loop {
+            let mut nodes = Vec::with_capacity(pod_names.len());
+            let mut all_ready = true;
+
+            for pod_name in pod_names {
+                let pod = api.get(pod_name).await.map_err(|e| {
+                    Error::new(
+                        ErrorKind::Other,
+                        format!("failed to get pod {} ({})", pod_name, e),
+                    )
+                })?;
+                let phase = pod
+                    .status
+                    .as_ref()
+                    .and_then(|s| s.phase.clone())
+                    .unwrap_or_default();
+                let pod_ip = pod.status.as_ref().and_then(|s| s.pod_ip.clone());
+
+                if phase != "Running" || pod_ip.is_none() {
+                    all_ready = false;
+                }
+
+                nodes.push(DiscoveredNode {
+                    pod_name: pod_name.clone(),
+                    http_endpoint: pod_ip
+                        .as_ref()
+                        .map(|ip| format!("http://{}:{}", ip, http_port)),
+                    pod_ip,
+                });
+            }
+
+            if all_ready {
+                return Ok(nodes);
+            }
+            if started.elapsed() >= deadline {
+                return Err(Error::new(
+                    ErrorKind::TimedOut,
+                    format!("pods not all ready after {:?}", deadline),
+                ));
+            }
+            sleep(Duration::from_secs(5)).await;
+        }
+    }
+
+    /// Deletes the `StatefulSet` and headless `Service` named `prefix`,
+    /// tolerating either being already gone. The `StatefulSet`'s own pods
+    /// are garbage-collected by Kubernetes once it is deleted.
+    pub async fn delete_nodes(&self, prefix: &str) -> io::Result<()> {
+        let sts_api: Api<StatefulSet> = Api::namespaced(self.client.clone(), &self.namespace);
+        info!(
+            "deleting statefulset {} in namespace {}",
+            prefix, self.namespace
+        );
+        match sts_api.delete(prefix, &DeleteParams::default()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("failed to delete statefulset {} ({})", prefix, e),
+                ));
+            }
+        }
+
+        let svc_api: Api<Service> = Api::namespaced(self.client.clone(), &self.namespace);
+        info!("deleting service {} in namespace {}", prefix, self.namespace);
+        match svc_api.delete(prefix, &DeleteParams::default()).await {
+            Ok(_) => {}
+            Err(kube::Error::Api(e)) if e.code == 404 => {}
+            Err(e) => {
+                return Err(Error::new(
+                    ErrorKind::Other,
+                    format!("failed to delete service {} ({})", prefix, e),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Kubernetes resources used for a single cluster/test, held in
+/// `Infra::Kubernetes` in place of `aws::Resources` when a deployment
+/// targets an existing Kubernetes cluster (a `StatefulSet` + headless
+/// `Service` per node group, discovered by label selector) instead of EC2.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct Resources {
+    /// Namespace the StatefulSets/Services/pods are created in.
+    #[serde(default)]
+    pub namespace: String,
+    /// Container image reference for the avalanchego nodes.
+    #[serde(default)]
+    pub image: String,
+    /// Replica count for the anchor-node `StatefulSet`. Mirrors
+    /// `Machine.anchor_nodes`; "None"/0 means no anchor node group is
+    /// created (non-custom networks).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchor_nodes: Option<u32>,
+    /// Replica count for the non-anchor-node `StatefulSet`. Mirrors
+    /// `Machine.non_anchor_nodes`.
+    #[serde(default)]
+    pub non_anchor_nodes: u32,
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashSet},
     fs::{self, File},
     io::{self, Error, ErrorKind, Write},
     path::Path,
@@ -8,12 +8,47 @@ use std::{
 
 use log::info;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 pub mod errors;
 
 /// ref. https://doc.rust-lang.org/reference/items/modules.html
 pub mod aws;
 
+/// Embedded HTTP admin API for introspecting/rescaling a running
+/// deployment. Gated behind the "admin" feature so the hyper server
+/// dependency isn't pulled in for consumers that only need the library.
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+#[cfg(feature = "admin")]
+pub mod admin;
+
+/// Opt-in post-bootstrap load/benchmark smoke test driven against a
+/// cluster's own C-chain RPC endpoint.
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod benchmark;
+
+/// Scans the "StorageNamespace" discovery prefixes for drift against
+/// reality (duplicate/orphaned discovery files, unreachable ready nodes,
+/// anchor nodes missing from genesis) and optionally repairs what it
+/// finds.
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+pub mod reconcile;
+
+/// Alternative deployment backend: the same logical resources as `aws`
+/// (`Spec::infra`'s `Infra::Kubernetes` variant), provisioned onto an
+/// existing Kubernetes cluster instead of via CloudFormation/EC2. Gated
+/// behind the "kubernetes" feature so non-K8s users pay no dependency cost.
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+#[cfg(feature = "kubernetes")]
+pub mod kubernetes;
+
+/// StatefulSet/Service-based discovery/provisioning operations (bring up N
+/// nodes, poll until ready, resolve endpoints, tear down) for the
+/// `kubernetes` backend.
+/// ref. https://doc.rust-lang.org/reference/items/modules.html
+#[cfg(feature = "kubernetes")]
+pub mod discovery;
+
 /// ref. https://doc.rust-lang.org/reference/items/modules.html
 pub mod utils;
 use crate::utils::{id, prefix, random, time};
@@ -44,6 +79,17 @@ pub const DEFAULT_MACHINE_NON_ANCHOR_NODES: u32 = 2;
 pub const MIN_MACHINE_NON_ANCHOR_NODES: u32 = 1;
 pub const MAX_MACHINE_NON_ANCHOR_NODES: u32 = 200; // TODO: allow higher number?
 
+/// Which infrastructure backend a deployment targets, and that backend's
+/// resources. "validate" and the provisioning flow branch on this instead
+/// of assuming AWS is the only target.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Infra {
+    Aws(aws::Resources),
+    #[cfg(feature = "kubernetes")]
+    Kubernetes(kubernetes::Resources),
+}
+
 /// Represents network-level configuration shared among all nodes.
 /// The node-level configuration is generated during each
 /// bootstrap process (e.g., certificates) and not defined
@@ -59,9 +105,9 @@ pub struct Spec {
     #[serde(default)]
     pub id: String,
 
-    /// AWS resources if run in AWS.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub aws_resources: Option<aws::Resources>,
+    /// Which infrastructure backend this cluster/test is deployed to, and
+    /// that backend's resources.
+    pub infra: Infra,
 
     /// Defines how the underlying infrastructure is set up.
     /// MUST BE NON-EMPTY.
@@ -82,7 +128,9 @@ pub struct Spec {
     /// and used for "--genesis" in Path::new(&avalanchego_config.genesis).
     /// This includes "coreth_genesis::Genesis".
     /// Names after "_template" since it has not included
-    /// initial stakers yet with to-be-created node IDs.
+    /// initial stakers yet with to-be-created node IDs, UNLESS
+    /// "node_key_seed" is set, in which case anchor node IDs are
+    /// deterministically derived up front and already included here.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub avalanchego_genesis_template: Option<avalanchego_genesis::Genesis>,
 
@@ -104,6 +152,34 @@ pub struct Spec {
     pub current_nodes: Option<Vec<node::Node>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoints: Option<Endpoints>,
+
+    /// Per-node region assignment, keyed by region and holding node
+    /// indices, only set when "machine.regions" is specified. Computed via
+    /// "Machine::assign_regions" and round-tripped by "sync"/"load" so a
+    /// later resize rebalances relative to this instead of from scratch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_region_assignment: Option<aws::placement::Assignment>,
+
+    /// Seed used to deterministically derive each node's staking TLS
+    /// key/cert and resulting NodeID (see "key::derive_staking_key_and_node_id"),
+    /// instead of generating them fresh at bootstrap. The same seed+index
+    /// always yields the same NodeID, so anchor node IDs can be baked into
+    /// "avalanchego_genesis_template"'s initial stakers before any machine
+    /// exists. "None" falls back to today's random post-bootstrap generation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node_key_seed: Option<String>,
+
+    /// Opt-in post-bootstrap load/benchmark smoke test config, and once
+    /// "Spec::run_benchmark" has run, its result. "None" means the
+    /// benchmark was never enabled for this cluster.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub benchmark: Option<benchmark::BenchmarkSpec>,
+
+    /// Custom subnets to create/register as part of this deployment. See
+    /// "validate" for the cross-checks run against "current_nodes",
+    /// "avalanchego_genesis_template", and the plugins manifest.
+    #[serde(default)]
+    pub subnets: Vec<SubnetSpec>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
@@ -176,6 +252,98 @@ pub struct Machine {
     pub non_anchor_nodes: u32,
     #[serde(default)]
     pub instance_types: Option<Vec<String>>,
+
+    /// Regions (with per-region node capacity) to spread the fleet across
+    /// for fault tolerance. If "None", every node lands in the single
+    /// region configured in "infra" as before.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regions: Option<Vec<RegionSpec>>,
+}
+
+/// A single region a fleet may be spread into, alongside how many nodes it
+/// may hold.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct RegionSpec {
+    pub region: String,
+    pub capacity: u32,
+}
+
+impl Machine {
+    /// Total node count this machine spec describes (anchor + non-anchor).
+    pub fn total_nodes(&self) -> u32 {
+        self.anchor_nodes.unwrap_or(0) + self.non_anchor_nodes
+    }
+
+    /// Computes a region -> node-index assignment balanced so every region
+    /// holds either `floor(N/R)` or `ceil(N/R)` nodes, never exceeding its
+    /// declared capacity. `existing` is the spec's current
+    /// `node_region_assignment` (empty for a first-time assignment); the
+    /// assignment is computed in *relative* terms off of it, so adding a
+    /// region to an existing spec only moves nodes off the most-loaded
+    /// regions until balance is reached, rather than recomputing from
+    /// scratch. Returns "None" if "regions" is not set.
+    pub fn assign_regions(
+        &self,
+        existing: &aws::placement::Assignment,
+    ) -> io::Result<Option<aws::placement::Assignment>> {
+        let regions = match &self.regions {
+            Some(r) => r,
+            None => return Ok(None),
+        };
+
+        let capacities: BTreeMap<String, usize> = regions
+            .iter()
+            .map(|r| (r.region.clone(), r.capacity as usize))
+            .collect();
+        let n = self.total_nodes() as usize;
+
+        let total_capacity: usize = capacities.values().sum();
+        if total_capacity < n {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "total region capacity {} is less than total node count {}",
+                    total_capacity, n
+                ),
+            ));
+        }
+
+        Ok(Some(aws::placement::rebalance(existing, n, &capacities)?))
+    }
+}
+
+#[test]
+fn test_assign_regions() {
+    let machine = Machine {
+        anchor_nodes: Some(2),
+        non_anchor_nodes: 4,
+        instance_types: None,
+        regions: None,
+    };
+    assert_eq!(machine.assign_regions(&BTreeMap::new()).unwrap(), None);
+
+    let machine = Machine {
+        anchor_nodes: Some(2),
+        non_anchor_nodes: 4,
+        instance_types: None,
+        regions: Some(vec![
+            RegionSpec { region: "us-west-2".to_string(), capacity: 10 },
+            RegionSpec { region: "us-east-1".to_string(), capacity: 10 },
+        ]),
+    };
+    let assignment = machine.assign_regions(&BTreeMap::new()).unwrap().unwrap();
+    let mut counts: Vec<usize> = assignment.values().map(|v| v.len()).collect();
+    counts.sort_unstable();
+    assert_eq!(counts, vec![3, 3]);
+
+    let under_capacity = Machine {
+        anchor_nodes: Some(2),
+        non_anchor_nodes: 4,
+        instance_types: None,
+        regions: Some(vec![RegionSpec { region: "us-west-2".to_string(), capacity: 1 }]),
+    };
+    assert!(under_capacity.assign_regions(&BTreeMap::new()).is_err());
 }
 
 /// Represents artifacts for installation, to be shared with
@@ -206,12 +374,262 @@ pub struct InstallArtifacts {
     pub plugins_dir: Option<String>,
 }
 
+/// A single plugin binary discovered under "InstallArtifacts.plugins_dir"
+/// by "manifest_plugins_dir", keyed by file name so each one can be
+/// uploaded to its own "StorageNamespace::PluginFile" S3 key and its
+/// integrity checked against "sha256" on download.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct PluginArtifact {
+    pub file_name: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+/// Recursively walks "plugins_dir" across threads (via "jwalk", which
+/// layers rayon/crossbeam over recursive "read_dir") and returns a
+/// manifest of every plugin found, descending into subdirectories and
+/// rejecting empty/non-file/unreadable entries along the way. Used by
+/// "Spec::validate" to catch a broken plugin set (a symlink or other
+/// non-regular file, a zero-byte upload, a permissions mistake) before a
+/// deploy, rather than failing much later on a remote machine.
+pub fn manifest_plugins_dir(plugins_dir: &str) -> io::Result<Vec<PluginArtifact>> {
+    let mut artifacts = Vec::new();
+    for entry in jwalk::WalkDir::new(plugins_dir) {
+        let entry = entry.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to walk plugins_dir {} ({})", plugins_dir, e),
+            )
+        })?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        if !entry.file_type().is_file() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("plugin entry {:?} is not a regular file", entry.path()),
+            ));
+        }
+
+        let path = entry.path();
+        let file_name = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Other,
+                    format!("failed to read file name of {:?}", path),
+                )
+            })?
+            .to_string();
+
+        let contents = fs::read(&path).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("failed to read plugin file {:?} ({})", path, e),
+            )
+        })?;
+        if contents.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("plugin file {:?} is empty", path),
+            ));
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(&contents);
+        let sha256 = hex::encode(hasher.finalize());
+
+        artifacts.push(PluginArtifact {
+            file_name,
+            size: contents.len() as u64,
+            sha256,
+        });
+    }
+
+    if artifacts.is_empty() {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("plugins_dir {} has no plugin files", plugins_dir),
+        ));
+    }
+
+    Ok(artifacts)
+}
+
+/// When a custom subnet's "SubnetSpec" gets created/registered relative to
+/// the rest of the deployment.
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationStrategy {
+    /// The operator creates/registers the subnet manually after deploy.
+    Manual,
+    /// Registered as part of network bootstrap, before any node is up.
+    AtNetworkBootstrap,
+    /// Registered only once all anchor nodes report ready. Only valid for
+    /// custom networks with "machine.anchor_nodes" set (see "validate").
+    AfterAnchorsReady,
+}
+
+/// Declares a single custom subnet to create as part of this deployment:
+/// which VM plugin backs it, its genesis, which nodes should validate it,
+/// and when it gets registered. "validate" cross-checks "vm_name" against
+/// the plugins manifest and "validator_node_ids" against declared nodes.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SubnetSpec {
+    pub name: String,
+    /// Must match a "PluginArtifact.file_name" under
+    /// "install_artifacts.plugins_dir".
+    pub vm_name: String,
+    pub genesis: subnet_evm_genesis::Genesis,
+    /// NodeIDs that should join this subnet as validators. Each one must
+    /// already be a declared node: present in "current_nodes", or (for
+    /// anchor nodes derived via "node_key_seed") in
+    /// "avalanchego_genesis_template.initial_stakers".
+    #[serde(default)]
+    pub validator_node_ids: Vec<String>,
+    pub registration_strategy: RegistrationStrategy,
+}
+
+/// Cross-checks every "SubnetSpec" in "subnets" against the rest of the
+/// deployment: each validator must reference an already-declared node,
+/// each "vm_name" must resolve against the plugins manifest, and
+/// "RegistrationStrategy::AfterAnchorsReady" may only be used when
+/// "network_has_ready_anchor_capability" (a custom network with a
+/// non-zero anchor node count). Pulled out of "Spec::validate" as a pure
+/// function so it's testable without constructing a full "Spec".
+fn validate_subnets(
+    subnets: &[SubnetSpec],
+    declared_node_ids: &HashSet<&str>,
+    plugin_file_names: &HashSet<String>,
+    network_has_ready_anchor_capability: bool,
+) -> io::Result<()> {
+    for subnet in subnets {
+        for node_id in &subnet.validator_node_ids {
+            if !declared_node_ids.contains(node_id.as_str()) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "subnet '{}' validator '{}' is not a declared node",
+                        subnet.name, node_id
+                    ),
+                ));
+            }
+        }
+        if !plugin_file_names.contains(&subnet.vm_name) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "subnet '{}' vm_name '{}' does not resolve against the plugins manifest",
+                    subnet.name, subnet.vm_name
+                ),
+            ));
+        }
+        if subnet.registration_strategy == RegistrationStrategy::AfterAnchorsReady
+            && !network_has_ready_anchor_capability
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "subnet '{}' uses 'RegistrationStrategy::AfterAnchorsReady' but \
+                     this is not a custom network with anchor nodes",
+                    subnet.name
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_validate_subnets() {
+    let subnet = |validator_node_ids: Vec<&str>,
+                  vm_name: &str,
+                  registration_strategy: RegistrationStrategy| {
+        SubnetSpec {
+            name: "test-subnet".to_string(),
+            vm_name: vm_name.to_string(),
+            genesis: subnet_evm_genesis::Genesis::default(),
+            validator_node_ids: validator_node_ids.into_iter().map(String::from).collect(),
+            registration_strategy,
+        }
+    };
+
+    let declared: HashSet<&str> = HashSet::from(["NodeID-1", "NodeID-2"]);
+    let plugins: HashSet<String> = HashSet::from(["subnetevm".to_string()]);
+
+    // happy path
+    assert!(validate_subnets(
+        &[subnet(
+            vec!["NodeID-1"],
+            "subnetevm",
+            RegistrationStrategy::Manual
+        )],
+        &declared,
+        &plugins,
+        false,
+    )
+    .is_ok());
+
+    // validator not declared
+    assert!(validate_subnets(
+        &[subnet(
+            vec!["NodeID-unknown"],
+            "subnetevm",
+            RegistrationStrategy::Manual
+        )],
+        &declared,
+        &plugins,
+        false,
+    )
+    .is_err());
+
+    // vm_name not in plugins manifest
+    assert!(validate_subnets(
+        &[subnet(vec!["NodeID-1"], "unknown-vm", RegistrationStrategy::Manual)],
+        &declared,
+        &plugins,
+        false,
+    )
+    .is_err());
+
+    // AfterAnchorsReady without anchor-node capability
+    assert!(validate_subnets(
+        &[subnet(
+            vec!["NodeID-1"],
+            "subnetevm",
+            RegistrationStrategy::AfterAnchorsReady
+        )],
+        &declared,
+        &plugins,
+        false,
+    )
+    .is_err());
+
+    // AfterAnchorsReady with anchor-node capability
+    assert!(validate_subnets(
+        &[subnet(
+            vec!["NodeID-1"],
+            "subnetevm",
+            RegistrationStrategy::AfterAnchorsReady
+        )],
+        &declared,
+        &plugins,
+        true,
+    )
+    .is_ok());
+}
+
 /// Represents the CloudFormation stack name.
+/// The ASG variants carry a region so a multi-region `Machine` gets one ASG
+/// stack per region instead of colliding on a single name.
 pub enum StackName {
     Ec2InstanceRole(String),
     Vpc(String),
-    AsgBeaconNodes(String),
-    AsgNonBeaconNodes(String),
+    AsgBeaconNodes(String, String),
+    AsgNonBeaconNodes(String, String),
 }
 
 impl StackName {
@@ -219,8 +637,10 @@ impl StackName {
         match self {
             StackName::Ec2InstanceRole(id) => format!("{}-ec2-instance-role", id),
             StackName::Vpc(id) => format!("{}-vpc", id),
-            StackName::AsgBeaconNodes(id) => format!("{}-asg-anchor-nodes", id),
-            StackName::AsgNonBeaconNodes(id) => format!("{}-asg-non-anchor-nodes", id),
+            StackName::AsgBeaconNodes(id, region) => format!("{}-asg-anchor-nodes-{}", id, region),
+            StackName::AsgNonBeaconNodes(id, region) => {
+                format!("{}-asg-non-anchor-nodes-{}", id, region)
+            }
         }
     }
 }
@@ -234,10 +654,35 @@ pub struct DefaultSpecOption {
 
     pub region: String,
 
+    /// If non-empty, used to deterministically derive each anchor node's
+    /// staking TLS key/cert and NodeID (see
+    /// "key::derive_staking_key_and_node_id"), so anchor NodeIDs are known
+    /// and baked into the genesis initial-stakers list before any machine
+    /// exists. Empty falls back to random generation at bootstrap.
+    pub node_key_seed: String,
+
+    /// Namespace to create Kubernetes resources in. Only used by
+    /// "default_kubernetes".
+    #[cfg(feature = "kubernetes")]
+    pub kubernetes_namespace: String,
+    /// Container image reference for the avalanchego nodes. Only used by
+    /// "default_kubernetes".
+    #[cfg(feature = "kubernetes")]
+    pub kubernetes_image: String,
+
     pub db_backup_s3_region: String,
     pub db_backup_s3_bucket: String,
     pub db_backup_s3_key: String,
 
+    /// Non-AWS, S3-compatible endpoint to upload/download "s3_bucket" and
+    /// the DB backup through (e.g. a MinIO/Garage/Wasabi URL). Empty uses
+    /// native AWS S3.
+    pub s3_endpoint: String,
+    /// Region to sign S3 requests with when "s3_endpoint" is non-empty.
+    /// Required whenever "s3_endpoint" is set.
+    pub s3_region: String,
+    pub s3_path_style_access: bool,
+
     pub nlb_acm_certificate_arn: String,
 
     pub install_artifacts_avalanched_bin: String,
@@ -259,148 +704,249 @@ pub struct DefaultSpecOption {
 
     pub enable_subnet_evm: bool,
 
+    /// If set, runs the built-in load/benchmark smoke test against the
+    /// cluster's C-chain RPC endpoint once "endpoints" is
+    /// populated (see "Spec::run_benchmark").
+    pub benchmark_enabled: bool,
+    pub benchmark_tx_rate: u32,
+    pub benchmark_duration_secs: u64,
+
     pub disable_instance_system_logs: bool,
     pub disable_instance_system_metrics: bool,
 
     pub spec_file_path: String,
 }
 
-impl Spec {
-    /// Creates a default Status based on the network ID.
-    /// For custom networks, it generates the "keys" number of keys
-    /// and pre-funds them in the genesis file path, which is
-    /// included in "InstallArtifacts.genesis_draft_file_path".
-    pub fn default_aws(opt: DefaultSpecOption) -> Self {
-        let network_id = match constants::NETWORK_NAME_TO_NETWORK_ID.get(opt.network_name.as_str())
-        {
-            Some(v) => *v,
-            None => avalanchego_config::DEFAULT_CUSTOM_NETWORK_ID,
-        };
+/// Holds everything about a default `Spec` that is independent of which
+/// infrastructure backend (`Infra::Aws` or `Infra::Kubernetes`) it targets.
+struct CoreSpecParts {
+    id: String,
+    machine: Machine,
+    install_artifacts: InstallArtifacts,
+    avalanchego_config: avalanchego_config::Config,
+    coreth_config: coreth_config::Config,
+    avalanchego_genesis_template: Option<avalanchego_genesis::Genesis>,
+    subnet_evm_genesis: Option<subnet_evm_genesis::Genesis>,
+    generated_seed_private_key_with_locked_p_chain_balance: Option<key::PrivateKeyInfo>,
+    generated_seed_private_keys: Option<Vec<key::PrivateKeyInfo>>,
+    node_key_seed: Option<String>,
+    benchmark: Option<benchmark::BenchmarkSpec>,
+}
 
-        let mut avalanchego_config = avalanchego_config::Config::default();
-        avalanchego_config.network_id = network_id;
-        avalanchego_config.log_level = Some(opt.avalanchego_log_level);
-        if !avalanchego_config.is_custom_network() {
-            avalanchego_config.genesis = None;
-        }
+/// Builds the infra-independent parts of a default `Spec` (network config,
+/// genesis, machine sizing, pre-funded keys). Shared by `default_aws` and
+/// `default_kubernetes` since neither depends on the underlying backend.
+fn build_core_spec_parts(opt: &DefaultSpecOption) -> CoreSpecParts {
+    let network_id = match constants::NETWORK_NAME_TO_NETWORK_ID.get(opt.network_name.as_str()) {
+        Some(v) => *v,
+        None => avalanchego_config::DEFAULT_CUSTOM_NETWORK_ID,
+    };
 
-        // only set values if non empty
-        // otherwise, avalanchego will fail with "couldn't load node config: read .: is a directory"
-        // TODO: use different certs than staking?
-        if opt.avalanchego_http_tls_enabled {
-            avalanchego_config.http_tls_enabled = Some(true);
-            avalanchego_config.http_tls_key_file = avalanchego_config.staking_tls_key_file.clone();
-            avalanchego_config.http_tls_cert_file =
-                avalanchego_config.staking_tls_cert_file.clone();
-        }
+    let mut avalanchego_config = avalanchego_config::Config::default();
+    avalanchego_config.network_id = network_id;
+    avalanchego_config.log_level = Some(opt.avalanchego_log_level.clone());
+    if !avalanchego_config.is_custom_network() {
+        avalanchego_config.genesis = None;
+    }
 
-        if !opt.avalanchego_state_sync_ids.is_empty() {
-            avalanchego_config.state_sync_ids = Some(opt.avalanchego_state_sync_ids.clone());
-        };
-        if !opt.avalanchego_state_sync_ips.is_empty() {
-            avalanchego_config.state_sync_ips = Some(opt.avalanchego_state_sync_ips.clone());
-        };
-        if opt.avalanchego_profile_continuous_enabled {
-            avalanchego_config.profile_continuous_enabled = Some(true);
-        }
-        if !opt.avalanchego_profile_continuous_freq.is_empty() {
-            avalanchego_config.profile_continuous_freq =
-                Some(opt.avalanchego_profile_continuous_freq.clone());
-        };
-        if !opt.avalanchego_profile_continuous_max_files.is_empty() {
-            let profile_continuous_max_files = opt.avalanchego_profile_continuous_max_files;
-            let profile_continuous_max_files = profile_continuous_max_files.parse::<u32>().unwrap();
-            avalanchego_config.profile_continuous_max_files = Some(profile_continuous_max_files);
-        };
-        if !opt.avalanchego_whitelisted_subnets.is_empty() {
-            avalanchego_config.whitelisted_subnets = Some(opt.avalanchego_whitelisted_subnets);
-        };
+    // only set values if non empty
+    // otherwise, avalanchego will fail with "couldn't load node config: read .: is a directory"
+    // TODO: use different certs than staking?
+    if opt.avalanchego_http_tls_enabled {
+        avalanchego_config.http_tls_enabled = Some(true);
+        avalanchego_config.http_tls_key_file = avalanchego_config.staking_tls_key_file.clone();
+        avalanchego_config.http_tls_cert_file = avalanchego_config.staking_tls_cert_file.clone();
+    }
 
-        let network_id = avalanchego_config.network_id;
-        let id = {
-            if !opt.spec_file_path.is_empty() {
-                let spec_file_stem = Path::new(&opt.spec_file_path).file_stem().unwrap();
-                spec_file_stem.to_str().unwrap().to_string()
-            } else {
-                match constants::NETWORK_ID_TO_NETWORK_NAME.get(&network_id) {
-                    Some(v) => id::with_time(format!("aops-{}", *v).as_str()),
-                    None => id::with_time("aops-custom"),
-                }
-            }
-        };
-        let (anchor_nodes, non_anchor_nodes) =
+    if !opt.avalanchego_state_sync_ids.is_empty() {
+        avalanchego_config.state_sync_ids = Some(opt.avalanchego_state_sync_ids.clone());
+    };
+    if !opt.avalanchego_state_sync_ips.is_empty() {
+        avalanchego_config.state_sync_ips = Some(opt.avalanchego_state_sync_ips.clone());
+    };
+    if opt.avalanchego_profile_continuous_enabled {
+        avalanchego_config.profile_continuous_enabled = Some(true);
+    }
+    if !opt.avalanchego_profile_continuous_freq.is_empty() {
+        avalanchego_config.profile_continuous_freq =
+            Some(opt.avalanchego_profile_continuous_freq.clone());
+    };
+    if !opt.avalanchego_profile_continuous_max_files.is_empty() {
+        let profile_continuous_max_files = opt.avalanchego_profile_continuous_max_files.clone();
+        let profile_continuous_max_files = profile_continuous_max_files.parse::<u32>().unwrap();
+        avalanchego_config.profile_continuous_max_files = Some(profile_continuous_max_files);
+    };
+    if !opt.avalanchego_whitelisted_subnets.is_empty() {
+        avalanchego_config.whitelisted_subnets = Some(opt.avalanchego_whitelisted_subnets.clone());
+    };
+
+    let network_id = avalanchego_config.network_id;
+    let id = {
+        if !opt.spec_file_path.is_empty() {
+            let spec_file_stem = Path::new(&opt.spec_file_path).file_stem().unwrap();
+            spec_file_stem.to_str().unwrap().to_string()
+        } else {
             match constants::NETWORK_ID_TO_NETWORK_NAME.get(&network_id) {
-                Some(_) => (None, DEFAULT_MACHINE_NON_ANCHOR_NODES),
-                None => (
-                    Some(DEFAULT_MACHINE_ANCHOR_NODES),
-                    DEFAULT_MACHINE_NON_ANCHOR_NODES,
-                ),
-            };
-        let machine = Machine {
-            anchor_nodes,
-            non_anchor_nodes,
-            instance_types: Some(vec![
-                String::from("c6a.large"),
-                String::from("m6a.large"),
-                String::from("m5.large"),
-                String::from("c5.large"),
-            ]),
-        };
+                Some(v) => id::with_time(format!("aops-{}", *v).as_str()),
+                None => id::with_time("aops-custom"),
+            }
+        }
+    };
+    let (anchor_nodes, non_anchor_nodes) = match constants::NETWORK_ID_TO_NETWORK_NAME
+        .get(&network_id)
+    {
+        Some(_) => (None, DEFAULT_MACHINE_NON_ANCHOR_NODES),
+        None => (
+            Some(DEFAULT_MACHINE_ANCHOR_NODES),
+            DEFAULT_MACHINE_NON_ANCHOR_NODES,
+        ),
+    };
+    let machine = Machine {
+        anchor_nodes,
+        non_anchor_nodes,
+        instance_types: Some(vec![
+            String::from("c6a.large"),
+            String::from("m6a.large"),
+            String::from("m5.large"),
+            String::from("c5.large"),
+        ]),
+        regions: None,
+    };
+
+    let node_key_seed = if opt.node_key_seed.is_empty() {
+        None
+    } else {
+        Some(opt.node_key_seed.clone())
+    };
 
-        let (avalanchego_genesis_template, generated_seed_keys) = {
-            if avalanchego_config.is_custom_network() {
-                let (g, seed_keys) =
-                    avalanchego_genesis::Genesis::new(network_id, opt.keys_to_generate)
-                        .expect("unexpected None genesis");
-                (Some(g), seed_keys)
-            } else {
-                // existing network has only 1 pre-funded key "ewoq"
-                let mut seed_keys: Vec<key::PrivateKeyInfo> = Vec::new();
-                for i in 0..opt.keys_to_generate {
-                    let k = {
-                        if i < key::TEST_KEYS.len() {
-                            key::TEST_KEYS[i].clone()
-                        } else {
-                            key::Key::generate().expect("unexpected key generate failure")
-                        }
-                    };
-                    let info = k.to_info(network_id).expect("unexpected to_info failure");
-                    seed_keys.push(info);
+    let (avalanchego_genesis_template, generated_seed_keys) = {
+        if avalanchego_config.is_custom_network() {
+            let (mut g, seed_keys) =
+                avalanchego_genesis::Genesis::new(network_id, opt.keys_to_generate)
+                    .expect("unexpected None genesis");
+
+            // pre-compute anchor node IDs from the seed so they can be baked
+            // into the genesis initial-stakers list before any machine
+            // exists, instead of patching the genesis after bootstrap
+            if let Some(seed) = &node_key_seed {
+                let anchor_node_count = machine.anchor_nodes.unwrap_or(0);
+                let mut initial_stakers = Vec::new();
+                for i in 0..anchor_node_count {
+                    let (_tls_key_pem, _tls_cert_pem, node_id) =
+                        key::derive_staking_key_and_node_id(seed, i as u64)
+                            .expect("unexpected deterministic key derivation failure");
+                    initial_stakers.push(avalanchego_genesis::Staker {
+                        node_id,
+                        ..avalanchego_genesis::Staker::default()
+                    });
                 }
-                (None, seed_keys)
+                g.initial_stakers = Some(initial_stakers);
             }
-        };
-        let generated_seed_private_key_with_locked_p_chain_balance =
-            Some(generated_seed_keys[0].clone());
-        let generated_seed_private_keys = Some(generated_seed_keys[1..].to_vec());
-
-        let subnet_evm_genesis = {
-            if opt.enable_subnet_evm {
-                let mut subnet_evm_seed_allocs = BTreeMap::new();
-                let mut admin_addresses: Vec<String> = Vec::new();
-                for key_info in generated_seed_keys.iter() {
-                    subnet_evm_seed_allocs.insert(
-                        String::from(prefix::strip_0x(&key_info.eth_address)),
-                        subnet_evm_genesis::AllocAccount::default(),
-                    );
-                    admin_addresses.push(key_info.eth_address.clone());
-                }
-                let mut genesis = subnet_evm_genesis::Genesis::default();
-                genesis.alloc = Some(subnet_evm_seed_allocs);
 
-                let mut chain_config = subnet_evm_genesis::ChainConfig::default();
-                let allow_list = subnet_evm_genesis::ContractDeployerAllowListConfig {
-                    allow_list_admins: Some(admin_addresses),
-                    ..subnet_evm_genesis::ContractDeployerAllowListConfig::default()
+            (Some(g), seed_keys)
+        } else {
+            // existing network has only 1 pre-funded key "ewoq"
+            let mut seed_keys: Vec<key::PrivateKeyInfo> = Vec::new();
+            for i in 0..opt.keys_to_generate {
+                let k = {
+                    if i < key::TEST_KEYS.len() {
+                        key::TEST_KEYS[i].clone()
+                    } else {
+                        key::Key::generate().expect("unexpected key generate failure")
+                    }
                 };
-                chain_config.contract_deployer_allow_list_config = Some(allow_list);
-                genesis.config = Some(chain_config);
-
-                Some(genesis)
-            } else {
-                None
+                let info = k.to_info(network_id).expect("unexpected to_info failure");
+                seed_keys.push(info);
             }
-        };
+            (None, seed_keys)
+        }
+    };
+    let generated_seed_private_key_with_locked_p_chain_balance =
+        Some(generated_seed_keys[0].clone());
+    let generated_seed_private_keys = Some(generated_seed_keys[1..].to_vec());
+
+    let subnet_evm_genesis = {
+        if opt.enable_subnet_evm {
+            let mut subnet_evm_seed_allocs = BTreeMap::new();
+            let mut admin_addresses: Vec<String> = Vec::new();
+            for key_info in generated_seed_keys.iter() {
+                subnet_evm_seed_allocs.insert(
+                    String::from(prefix::strip_0x(&key_info.eth_address)),
+                    subnet_evm_genesis::AllocAccount::default(),
+                );
+                admin_addresses.push(key_info.eth_address.clone());
+            }
+            let mut genesis = subnet_evm_genesis::Genesis::default();
+            genesis.alloc = Some(subnet_evm_seed_allocs);
+
+            let mut chain_config = subnet_evm_genesis::ChainConfig::default();
+            let allow_list = subnet_evm_genesis::ContractDeployerAllowListConfig {
+                allow_list_admins: Some(admin_addresses),
+                ..subnet_evm_genesis::ContractDeployerAllowListConfig::default()
+            };
+            chain_config.contract_deployer_allow_list_config = Some(allow_list);
+            genesis.config = Some(chain_config);
+
+            Some(genesis)
+        } else {
+            None
+        }
+    };
+
+    let mut install_artifacts = InstallArtifacts {
+        avalanched_bin: opt.install_artifacts_avalanched_bin.clone(),
+        avalanchego_bin: opt.install_artifacts_avalanche_bin.clone(),
+        plugins_dir: None,
+    };
+    if !opt.install_artifacts_plugins_dir.is_empty() {
+        install_artifacts.plugins_dir = Some(opt.install_artifacts_plugins_dir.clone());
+    }
+
+    let mut coreth_config = coreth_config::Config::default();
+    if opt.coreth_metrics_enabled {
+        coreth_config.metrics_enabled = Some(true);
+    }
+    if opt.coreth_continuous_profiler_enabled {
+        coreth_config.continuous_profiler_dir = Some(String::from(coreth_config::DEFAULT_PROFILE_DIR));
+        coreth_config.continuous_profiler_frequency = Some(coreth_config::DEFAULT_PROFILE_FREQUENCY);
+        coreth_config.continuous_profiler_max_files = Some(coreth_config::DEFAULT_PROFILE_MAX_FILES);
+    }
+    if opt.coreth_offline_pruning_enabled {
+        coreth_config.offline_pruning_enabled = Some(true);
+    }
+
+    let benchmark = if opt.benchmark_enabled {
+        Some(benchmark::BenchmarkSpec {
+            tx_rate: opt.benchmark_tx_rate,
+            duration_secs: opt.benchmark_duration_secs,
+            result: None,
+        })
+    } else {
+        None
+    };
+
+    CoreSpecParts {
+        id,
+        machine,
+        install_artifacts,
+        avalanchego_config,
+        coreth_config,
+        avalanchego_genesis_template,
+        subnet_evm_genesis,
+        generated_seed_private_key_with_locked_p_chain_balance,
+        generated_seed_private_keys,
+        node_key_seed,
+        benchmark,
+    }
+}
+
+impl Spec {
+    /// Creates a default Spec targeting AWS. For custom networks, it
+    /// generates the "keys" number of keys and pre-funds them in the
+    /// genesis file path, which is included in
+    /// "InstallArtifacts.genesis_draft_file_path".
+    pub fn default_aws(opt: DefaultSpecOption) -> Self {
+        let core = build_core_spec_parts(&opt);
 
         let mut aws_resources = aws::Resources {
             region: opt.region,
@@ -416,6 +962,15 @@ impl Spec {
         if !opt.db_backup_s3_key.is_empty() {
             aws_resources.db_backup_s3_key = Some(opt.db_backup_s3_key);
         }
+        if !opt.s3_endpoint.is_empty() {
+            aws_resources.s3_endpoint = Some(opt.s3_endpoint);
+        }
+        if !opt.s3_region.is_empty() {
+            aws_resources.s3_region = Some(opt.s3_region);
+        }
+        if opt.s3_path_style_access {
+            aws_resources.s3_path_style_access = Some(true);
+        }
         if !opt.nlb_acm_certificate_arn.is_empty() {
             aws_resources.nlb_acm_certificate_arn = Some(opt.nlb_acm_certificate_arn);
         }
@@ -425,51 +980,70 @@ impl Spec {
         if opt.disable_instance_system_metrics {
             aws_resources.instance_system_metrics = Some(false);
         }
-        let aws_resources = Some(aws_resources);
 
-        let mut install_artifacts = InstallArtifacts {
-            avalanched_bin: opt.install_artifacts_avalanched_bin,
-            avalanchego_bin: opt.install_artifacts_avalanche_bin,
-            plugins_dir: None,
-        };
-        if !opt.install_artifacts_plugins_dir.is_empty() {
-            install_artifacts.plugins_dir = Some(opt.install_artifacts_plugins_dir);
-        }
+        Self {
+            id: core.id,
 
-        let mut coreth_config = coreth_config::Config::default();
-        if opt.coreth_metrics_enabled {
-            coreth_config.metrics_enabled = Some(true);
-        }
-        if opt.coreth_continuous_profiler_enabled {
-            coreth_config.continuous_profiler_dir =
-                Some(String::from(coreth_config::DEFAULT_PROFILE_DIR));
-            coreth_config.continuous_profiler_frequency =
-                Some(coreth_config::DEFAULT_PROFILE_FREQUENCY);
-            coreth_config.continuous_profiler_max_files =
-                Some(coreth_config::DEFAULT_PROFILE_MAX_FILES);
-        }
-        if opt.coreth_offline_pruning_enabled {
-            coreth_config.offline_pruning_enabled = Some(true);
+            infra: Infra::Aws(aws_resources),
+            machine: core.machine,
+            install_artifacts: core.install_artifacts,
+
+            avalanchego_config: core.avalanchego_config,
+            coreth_config: core.coreth_config,
+            avalanchego_genesis_template: core.avalanchego_genesis_template,
+
+            subnet_evm_genesis: core.subnet_evm_genesis,
+
+            generated_seed_private_key_with_locked_p_chain_balance: core
+                .generated_seed_private_key_with_locked_p_chain_balance,
+            generated_seed_private_keys: core.generated_seed_private_keys,
+
+            current_nodes: None,
+            endpoints: None,
+            node_region_assignment: None,
+            node_key_seed: core.node_key_seed,
+            benchmark: core.benchmark,
+            subnets: Vec::new(),
         }
+    }
+
+    /// Creates a default Spec targeting an existing Kubernetes cluster
+    /// instead of AWS. Shares the same network/genesis/machine-sizing
+    /// logic as `default_aws`; only the `Infra` differs.
+    #[cfg(feature = "kubernetes")]
+    pub fn default_kubernetes(opt: DefaultSpecOption) -> Self {
+        let core = build_core_spec_parts(&opt);
+
+        let kubernetes_resources = kubernetes::Resources {
+            namespace: opt.kubernetes_namespace,
+            image: opt.kubernetes_image,
+            anchor_nodes: core.machine.anchor_nodes,
+            non_anchor_nodes: core.machine.non_anchor_nodes,
+        };
 
         Self {
-            id,
+            id: core.id,
 
-            aws_resources,
-            machine,
-            install_artifacts,
+            infra: Infra::Kubernetes(kubernetes_resources),
+            machine: core.machine,
+            install_artifacts: core.install_artifacts,
 
-            avalanchego_config,
-            coreth_config,
-            avalanchego_genesis_template,
+            avalanchego_config: core.avalanchego_config,
+            coreth_config: core.coreth_config,
+            avalanchego_genesis_template: core.avalanchego_genesis_template,
 
-            subnet_evm_genesis,
+            subnet_evm_genesis: core.subnet_evm_genesis,
 
-            generated_seed_private_key_with_locked_p_chain_balance,
-            generated_seed_private_keys,
+            generated_seed_private_key_with_locked_p_chain_balance: core
+                .generated_seed_private_key_with_locked_p_chain_balance,
+            generated_seed_private_keys: core.generated_seed_private_keys,
 
             current_nodes: None,
             endpoints: None,
+            node_region_assignment: None,
+            node_key_seed: core.node_key_seed,
+            benchmark: core.benchmark,
+            subnets: Vec::new(),
         }
     }
 
@@ -547,52 +1121,77 @@ impl Spec {
             ));
         }
 
-        if self.aws_resources.is_some() {
-            let aws_resources = self.aws_resources.clone().unwrap();
-            if aws_resources.region.is_empty() {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    "'machine.region' cannot be empty",
-                ));
-            }
-            if aws_resources.db_backup_s3_region.is_some()
-                && aws_resources.db_backup_s3_bucket.is_none()
-            {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    format!(
-                        "{} missing corresponding bucket",
-                        aws_resources
-                            .db_backup_s3_bucket
-                            .expect("unexpected aws_resources.db_backup_s3_bucket")
-                    ),
-                ));
-            }
-            if aws_resources.db_backup_s3_bucket.is_some()
-                && aws_resources.db_backup_s3_key.is_none()
-            {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    format!(
-                        "{} missing corresponding key",
-                        aws_resources
-                            .db_backup_s3_bucket
-                            .expect("unexpected aws_resources.db_backup_s3_bucket")
-                    ),
-                ));
+        match &self.infra {
+            Infra::Aws(aws_resources) => {
+                if aws_resources.region.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "'infra.aws.region' cannot be empty",
+                    ));
+                }
+                if aws_resources.s3_endpoint.is_some() && aws_resources.s3_region.is_none() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "'infra.aws.s3_endpoint' requires 'infra.aws.s3_region' to be set",
+                    ));
+                }
+                if aws_resources.db_backup_s3_region.is_some()
+                    && aws_resources.db_backup_s3_bucket.is_none()
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "{} missing corresponding bucket",
+                            aws_resources
+                                .db_backup_s3_region
+                                .as_ref()
+                                .expect("unexpected aws_resources.db_backup_s3_region")
+                        ),
+                    ));
+                }
+                if aws_resources.db_backup_s3_bucket.is_some()
+                    && aws_resources.db_backup_s3_key.is_none()
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "{} missing corresponding key",
+                            aws_resources
+                                .db_backup_s3_bucket
+                                .as_ref()
+                                .expect("unexpected aws_resources.db_backup_s3_bucket")
+                        ),
+                    ));
+                }
+                if aws_resources.db_backup_s3_bucket.is_some()
+                    && aws_resources.db_backup_s3_region.is_none()
+                {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "{} missing corresponding region",
+                            aws_resources
+                                .db_backup_s3_bucket
+                                .as_ref()
+                                .expect("unexpected aws_resources.db_backup_s3_bucket")
+                        ),
+                    ));
+                }
             }
-            if aws_resources.db_backup_s3_bucket.is_some()
-                && aws_resources.db_backup_s3_region.is_none()
-            {
-                return Err(Error::new(
-                    ErrorKind::InvalidInput,
-                    format!(
-                        "{} missing corresponding region",
-                        aws_resources
-                            .db_backup_s3_bucket
-                            .expect("unexpected aws_resources.db_backup_s3_bucket")
-                    ),
-                ));
+            #[cfg(feature = "kubernetes")]
+            Infra::Kubernetes(kubernetes_resources) => {
+                if kubernetes_resources.namespace.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "'infra.kubernetes.namespace' cannot be empty",
+                    ));
+                }
+                if kubernetes_resources.image.is_empty() {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "'infra.kubernetes.image' cannot be empty",
+                    ));
+                }
             }
         }
 
@@ -615,6 +1214,20 @@ impl Spec {
             ));
         }
 
+        if let Some(regions) = &self.machine.regions {
+            let total_capacity: u32 = regions.iter().map(|r| r.capacity).sum();
+            let total_nodes = self.machine.total_nodes();
+            if total_capacity < total_nodes {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "'machine.regions' total capacity {} is less than total node count {}",
+                        total_capacity, total_nodes
+                    ),
+                ));
+            }
+        }
+
         if !Path::new(&self.install_artifacts.avalanched_bin).exists() {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
@@ -633,26 +1246,17 @@ impl Spec {
                 ),
             ));
         }
-        if self.install_artifacts.plugins_dir.is_some()
-            && !Path::new(
-                &self
-                    .install_artifacts
-                    .plugins_dir
-                    .clone()
-                    .expect("unexpected None install_artifacts.plugins_dir"),
-            )
-            .exists()
-        {
-            return Err(Error::new(
-                ErrorKind::InvalidInput,
-                format!(
-                    "plugins_dir {} does not exist",
-                    self.install_artifacts
-                        .plugins_dir
-                        .clone()
-                        .expect("unexpected None install_artifacts.plugins_dir")
-                ),
-            ));
+        if let Some(plugins_dir) = &self.install_artifacts.plugins_dir {
+            if !Path::new(plugins_dir).exists() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("plugins_dir {} does not exist", plugins_dir),
+                ));
+            }
+            // walks the whole directory tree (not just its top level) so a
+            // plugin nested in a subdirectory, or a broken entry among
+            // dozens of plugins, is caught here rather than at deploy time
+            manifest_plugins_dir(plugins_dir)?;
         }
 
         if !self.avalanchego_config.is_custom_network() {
@@ -712,6 +1316,87 @@ impl Spec {
             }
         }
 
+        if !self.subnets.is_empty() {
+            let mut declared_node_ids: HashSet<&str> = self
+                .current_nodes
+                .as_ref()
+                .map(|nodes| nodes.iter().map(|n| n.node_id.as_str()).collect())
+                .unwrap_or_default();
+            if let Some(genesis) = &self.avalanchego_genesis_template {
+                if let Some(stakers) = &genesis.initial_stakers {
+                    declared_node_ids.extend(stakers.iter().map(|s| s.node_id.as_str()));
+                }
+            }
+
+            let plugin_file_names: HashSet<String> = match &self.install_artifacts.plugins_dir {
+                Some(plugins_dir) => manifest_plugins_dir(plugins_dir)?
+                    .into_iter()
+                    .map(|a| a.file_name)
+                    .collect(),
+                None => HashSet::new(),
+            };
+
+            validate_subnets(
+                &self.subnets,
+                &declared_node_ids,
+                &plugin_file_names,
+                self.avalanchego_config.is_custom_network()
+                    && self.machine.anchor_nodes.unwrap_or(0) > 0,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes "node_region_assignment" from "machine.assign_regions",
+    /// relative to whatever assignment is currently stored, and writes the
+    /// result back onto "self" so a later "sync" round-trips it. No-op if
+    /// "machine.regions" is not set. Callers that change "machine"'s node
+    /// counts (e.g. the admin API's "PUT /v2/machine") should call this
+    /// before "validate" so the stored assignment never drifts from the
+    /// node counts it was computed for.
+    pub fn plan_regions(&mut self) -> io::Result<()> {
+        let existing = self.node_region_assignment.clone().unwrap_or_default();
+        self.node_region_assignment = self.machine.assign_regions(&existing)?;
+        Ok(())
+    }
+
+    /// Runs the opt-in post-bootstrap benchmark (see "benchmark::run")
+    /// against this cluster's C-chain RPC endpoint and records
+    /// the result onto "self.benchmark". No-op if "benchmark" was never
+    /// enabled.
+    pub async fn run_benchmark(&mut self) -> io::Result<()> {
+        let bench_spec = match &self.benchmark {
+            Some(b) => b.clone(),
+            None => return Ok(()),
+        };
+        let endpoints = self.endpoints.clone().ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "cannot run benchmark before 'endpoints' is populated",
+            )
+        })?;
+        let rpc_c_chain = endpoints.http_rpc_c.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "'endpoints.http_rpc_c' is required to run benchmark",
+            )
+        })?;
+        let rpc_x_chain = endpoints.http_rpc_x.unwrap_or_default();
+        let seed_keys = self.generated_seed_private_keys.clone().unwrap_or_default();
+
+        let result = benchmark::run(
+            &rpc_c_chain,
+            &rpc_x_chain,
+            &seed_keys,
+            bench_spec.tx_rate,
+            bench_spec.duration_secs,
+        )
+        .await?;
+
+        if let Some(b) = &mut self.benchmark {
+            b.result = Some(result);
+        }
         Ok(())
     }
 }
@@ -753,11 +1438,12 @@ fn test_spec() {
 
 id: {}
 
-aws_resources:
-  region: us-west-2
-  s3_bucket: {}
-  instance_system_logs: true
-  instance_system_metrics: true
+infra:
+  aws:
+    region: us-west-2
+    s3_bucket: {}
+    instance_system_logs: true
+    instance_system_metrics: true
 
 machine:
   non_anchor_nodes: 20
@@ -828,9 +1514,11 @@ coreth_config:
     let orig = Spec {
         id: id.clone(),
 
-        aws_resources: Some(aws::Resources {
+        infra: Infra::Aws(aws::Resources {
             region: String::from("us-west-2"),
             s3_bucket: bucket.clone(),
+            instance_system_logs: Some(true),
+            instance_system_metrics: Some(true),
             ..aws::Resources::default()
         }),
 
@@ -843,6 +1531,7 @@ coreth_config:
                 String::from("r5.large"),
                 String::from("t3.large"),
             ]),
+            regions: None,
         },
 
         install_artifacts: InstallArtifacts {
@@ -861,6 +1550,10 @@ coreth_config:
         generated_seed_private_keys: None,
         current_nodes: None,
         endpoints: None,
+        node_region_assignment: None,
+        node_key_seed: None,
+        benchmark: None,
+        subnets: Vec::new(),
     };
 
     assert_eq!(cfg, orig);
@@ -870,7 +1563,11 @@ coreth_config:
     // manually check to make sure the serde deserializer works
     assert_eq!(cfg.id, id);
 
-    let aws_resources = cfg.aws_resources.unwrap();
+    let aws_resources = match cfg.infra {
+        Infra::Aws(aws_resources) => aws_resources,
+        #[cfg(feature = "kubernetes")]
+        _ => panic!("unexpected infra variant"),
+    };
     assert_eq!(aws_resources.region, "us-west-2");
     assert_eq!(aws_resources.s3_bucket, bucket);
 
@@ -936,6 +1633,10 @@ pub enum StorageNamespace {
     AvalanchedBin(String),
     AvalancheBinCompressed(String),
     PluginsDir(String),
+    /// A single plugin binary within "PluginsDir", keyed by file name so
+    /// each plugin gets its own S3 key and can be uploaded/resumed
+    /// independently instead of as one opaque directory blob.
+    PluginFile(String, String),
 
     PkiKeyDir(String),
 
@@ -955,6 +1656,10 @@ pub enum StorageNamespace {
 
     BackupsDir(String),
 
+    /// Per-subnet created subnet ID and config, published so nodes listed
+    /// in "SubnetSpec.validator_node_ids" can pick it up and join.
+    SubnetConfig(String, String),
+
     /// If this "event" file has been modified for the last x-min,
     /// avalanched triggers updates events based on the install artifacts
     /// in "EventsUpdateArtifactsInstallDir"
@@ -979,18 +1684,21 @@ impl StorageNamespace {
                 format!("{}/install/avalanche.zstd", id)
             }
             StorageNamespace::PluginsDir(id) => format!("{}/install/plugins", id),
+            StorageNamespace::PluginFile(id, file_name) => {
+                format!("{}/install/plugins/{}", id, file_name)
+            }
 
             StorageNamespace::PkiKeyDir(id) => {
                 format!("{}/pki", id)
             }
 
             StorageNamespace::DiscoverProvisioningAnchorNodesDir(id) => {
-                format!("{}/discover/provisioning-non-anchor-nodes", id)
+                format!("{}/discover/provisioning-anchor-nodes", id)
             }
             StorageNamespace::DiscoverProvisioningAnchorNode(id, node) => {
                 let compressed_id = node.compress_base58().unwrap();
                 format!(
-                    "{}/discover/provisioning-non-anchor-nodes/{}_{}.yaml",
+                    "{}/discover/provisioning-anchor-nodes/{}_{}.yaml",
                     id, node.machine_id, compressed_id
                 )
             }
@@ -1041,6 +1749,10 @@ impl StorageNamespace {
                 format!("{}/backups", id)
             }
 
+            StorageNamespace::SubnetConfig(id, subnet_name) => {
+                format!("{}/subnets/{}/config.json", id, subnet_name)
+            }
+
             StorageNamespace::EventsUpdateArtifactsEvent(id) => {
                 format!("{}/events/update-artifacts/event", id)
             }
@@ -0,0 +1,406 @@
+use std::{
+    collections::HashSet,
+    io::{self, Error, ErrorKind},
+    time::Duration,
+};
+
+use aws_sdk_s3::Client as S3Client;
+use log::{info, warn};
+use serde::Serialize;
+
+use crate::{aws::health, node, Spec, StorageNamespace};
+
+/// Which discovery prefix a node's file currently lives under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Prefix {
+    ProvisioningAnchor,
+    ProvisioningNonAnchor,
+    BootstrappingAnchor,
+    ReadyAnchor,
+    ReadyNonAnchor,
+}
+
+impl Prefix {
+    fn is_ready(&self) -> bool {
+        matches!(self, Prefix::ReadyAnchor | Prefix::ReadyNonAnchor)
+    }
+
+    fn is_anchor(&self) -> bool {
+        matches!(
+            self,
+            Prefix::ProvisioningAnchor | Prefix::BootstrappingAnchor | Prefix::ReadyAnchor
+        )
+    }
+}
+
+/// One discovery file found under a "StorageNamespace" prefix, decoded via
+/// "StorageNamespace::parse_node_from_path".
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscoveredFile {
+    pub key: String,
+    pub prefix: Prefix,
+    pub node: node::Node,
+}
+
+/// A single piece of drift between the S3 coordination state and reality.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Drift {
+    /// The same node_id has a discovery file under both a non-ready
+    /// prefix (provisioning/bootstrapping) and a "ready" prefix; the
+    /// non-ready key is stale and should be deleted.
+    DuplicateNode { node_id: String, stale_key: String },
+    /// A "ready" node whose HTTP endpoint did not answer a health check.
+    UnreachableReadyNode { node_id: String, key: String },
+    /// A discovery file whose "machine_id" doesn't match any currently
+    /// live EC2 instance.
+    OrphanedDiscoveryFile { machine_id: String, key: String },
+    /// A "ready" anchor node that isn't in the genesis initial-stakers
+    /// list.
+    MissingAnchorStaker { node_id: String },
+    /// A node filed under a non-ready prefix (provisioning/bootstrapping)
+    /// whose HTTP endpoint already answers healthy -- it should be
+    /// promoted to its matching "Ready*" prefix instead of being left
+    /// behind under the stale key forever.
+    StuckNode {
+        node_id: String,
+        stale_key: String,
+        new_key: String,
+    },
+}
+
+/// Structured result of "scan", either reported to the operator as-is or
+/// passed to "repair" to heal what it found.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ReconcileReport {
+    pub drifts: Vec<Drift>,
+}
+
+impl ReconcileReport {
+    pub fn is_clean(&self) -> bool {
+        self.drifts.is_empty()
+    }
+}
+
+/// Scans every "discover/provisioning-*", "discover/bootstrapping-anchor-nodes",
+/// and "discover/ready-*" key under "id" in "s3_bucket", parses each via
+/// "StorageNamespace::parse_node_from_path", and reports drift: a node
+/// present in both a non-ready and a ready prefix, an unreachable ready
+/// node, an orphaned discovery file (its "machine_id" isn't in
+/// "live_machine_ids"), and anchor nodes missing from the genesis
+/// initial-stakers list.
+pub async fn scan(
+    s3_cli: &S3Client,
+    s3_bucket: &str,
+    id: &str,
+    spec: &Spec,
+    live_machine_ids: &HashSet<String>,
+) -> io::Result<ReconcileReport> {
+    let prefixes = [
+        (
+            StorageNamespace::DiscoverProvisioningAnchorNodesDir(id.to_string()).encode(),
+            Prefix::ProvisioningAnchor,
+        ),
+        (
+            StorageNamespace::DiscoverProvisioningNonAnchorNodesDir(id.to_string()).encode(),
+            Prefix::ProvisioningNonAnchor,
+        ),
+        (
+            StorageNamespace::DiscoverBootstrappingAnchorNodesDir(id.to_string()).encode(),
+            Prefix::BootstrappingAnchor,
+        ),
+        (
+            StorageNamespace::DiscoverReadyAnchorNodesDir(id.to_string()).encode(),
+            Prefix::ReadyAnchor,
+        ),
+        (
+            StorageNamespace::DiscoverReadyNonAnchorNodesDir(id.to_string()).encode(),
+            Prefix::ReadyNonAnchor,
+        ),
+    ];
+
+    let mut files = Vec::new();
+    for (prefix, kind) in prefixes {
+        for key in list_keys(s3_cli, s3_bucket, &prefix).await? {
+            match StorageNamespace::parse_node_from_path(&key) {
+                Ok(node) => files.push(DiscoveredFile { key, prefix: kind, node }),
+                Err(e) => warn!("skipping undecodable discovery key {} ({})", key, e),
+            }
+        }
+    }
+
+    let mut drifts = Vec::new();
+    let duplicate_drifts = find_duplicate_nodes(&files);
+    let already_stale_keys: HashSet<&str> = duplicate_drifts
+        .iter()
+        .map(|d| match d {
+            Drift::DuplicateNode { stale_key, .. } => stale_key.as_str(),
+            _ => unreachable!("find_duplicate_nodes only returns Drift::DuplicateNode"),
+        })
+        .collect();
+    drifts.extend(duplicate_drifts);
+    drifts.extend(find_unreachable_ready_nodes(&files).await);
+    drifts.extend(find_orphaned_discovery_files(&files, live_machine_ids));
+    drifts.extend(find_missing_anchor_stakers(&files, spec));
+    drifts.extend(find_stuck_nodes(&files, id, &already_stale_keys).await);
+
+    info!(
+        "reconcile scan for {} found {} piece(s) of drift across {} discovery file(s)",
+        id,
+        drifts.len(),
+        files.len()
+    );
+    Ok(ReconcileReport { drifts })
+}
+
+fn find_duplicate_nodes(files: &[DiscoveredFile]) -> Vec<Drift> {
+    let ready_node_ids: HashSet<&str> = files
+        .iter()
+        .filter(|f| f.prefix.is_ready())
+        .map(|f| f.node.node_id.as_str())
+        .collect();
+
+    files
+        .iter()
+        .filter(|f| !f.prefix.is_ready() && ready_node_ids.contains(f.node.node_id.as_str()))
+        .map(|f| Drift::DuplicateNode {
+            node_id: f.node.node_id.clone(),
+            stale_key: f.key.clone(),
+        })
+        .collect()
+}
+
+async fn find_unreachable_ready_nodes(files: &[DiscoveredFile]) -> Vec<Drift> {
+    let mut drifts = Vec::new();
+    for f in files.iter().filter(|f| f.prefix.is_ready()) {
+        let reachable = health::poll_cluster(
+            vec![f.node.http_endpoint.clone()],
+            health::ClusterCondition::AllHealthy,
+            Duration::from_secs(3),
+        )
+        .await
+        .map(|h| h.condition_met)
+        .unwrap_or(false);
+
+        if !reachable {
+            drifts.push(Drift::UnreachableReadyNode {
+                node_id: f.node.node_id.clone(),
+                key: f.key.clone(),
+            });
+        }
+    }
+    drifts
+}
+
+fn find_orphaned_discovery_files(
+    files: &[DiscoveredFile],
+    live_machine_ids: &HashSet<String>,
+) -> Vec<Drift> {
+    files
+        .iter()
+        .filter(|f| !live_machine_ids.contains(&f.node.machine_id))
+        .map(|f| Drift::OrphanedDiscoveryFile {
+            machine_id: f.node.machine_id.clone(),
+            key: f.key.clone(),
+        })
+        .collect()
+}
+
+fn find_missing_anchor_stakers(files: &[DiscoveredFile], spec: &Spec) -> Vec<Drift> {
+    let staker_node_ids: HashSet<&str> = match &spec.avalanchego_genesis_template {
+        Some(genesis) => genesis
+            .initial_stakers
+            .as_ref()
+            .map(|stakers| stakers.iter().map(|s| s.node_id.as_str()).collect())
+            .unwrap_or_default(),
+        None => return Vec::new(),
+    };
+
+    files
+        .iter()
+        .filter(|f| f.prefix == Prefix::ReadyAnchor)
+        .filter(|f| !staker_node_ids.contains(f.node.node_id.as_str()))
+        .map(|f| Drift::MissingAnchorStaker {
+            node_id: f.node.node_id.clone(),
+        })
+        .collect()
+}
+
+/// A non-ready node is "stuck" once its HTTP endpoint answers healthy but
+/// its discovery file is still filed under a provisioning/bootstrapping
+/// prefix -- e.g. "avalanched" promoted it locally but the S3 write to the
+/// "Ready*" prefix never landed. "repair" re-publishes it under the
+/// matching "Ready*" key and removes the stale one.
+///
+/// "duplicate_stale_keys" are keys already reported by "find_duplicate_nodes"
+/// -- that node already has a live copy under a "Ready*" prefix, so its
+/// non-ready copy is stale rather than stuck, and "repair" will delete it
+/// directly instead of promoting it.
+async fn find_stuck_nodes(
+    files: &[DiscoveredFile],
+    id: &str,
+    duplicate_stale_keys: &HashSet<&str>,
+) -> Vec<Drift> {
+    let mut drifts = Vec::new();
+    for f in files
+        .iter()
+        .filter(|f| !f.prefix.is_ready() && !duplicate_stale_keys.contains(f.key.as_str()))
+    {
+        let healthy = health::poll_cluster(
+            vec![f.node.http_endpoint.clone()],
+            health::ClusterCondition::AllHealthy,
+            Duration::from_secs(3),
+        )
+        .await
+        .map(|h| h.condition_met)
+        .unwrap_or(false);
+
+        if healthy {
+            let new_key = if f.prefix.is_anchor() {
+                StorageNamespace::DiscoverReadyAnchorNode(id.to_string(), f.node.clone()).encode()
+            } else {
+                StorageNamespace::DiscoverReadyNonAnchorNode(id.to_string(), f.node.clone())
+                    .encode()
+            };
+            drifts.push(Drift::StuckNode {
+                node_id: f.node.node_id.clone(),
+                stale_key: f.key.clone(),
+                new_key,
+            });
+        }
+    }
+    drifts
+}
+
+/// Deletes every stale/orphaned discovery key found by "scan", and
+/// promotes every "StuckNode" to its matching "Ready*" prefix.
+/// Unreachable-node and missing-staker drift are reported but not
+/// auto-repaired since they require re-provisioning or a genesis update
+/// rather than an S3 write/delete.
+pub async fn repair(s3_cli: &S3Client, s3_bucket: &str, report: &ReconcileReport) -> io::Result<()> {
+    for drift in &report.drifts {
+        match drift {
+            Drift::DuplicateNode { stale_key, .. } => {
+                info!("repair: deleting stale discovery key {}", stale_key);
+                delete_key(s3_cli, s3_bucket, stale_key).await?;
+            }
+            Drift::OrphanedDiscoveryFile { key, .. } => {
+                info!("repair: deleting orphaned discovery key {}", key);
+                delete_key(s3_cli, s3_bucket, key).await?;
+            }
+            Drift::UnreachableReadyNode { node_id, .. } => {
+                warn!(
+                    "repair: node {} is unreachable; re-provision it, this cannot be auto-repaired",
+                    node_id
+                );
+            }
+            Drift::MissingAnchorStaker { node_id } => {
+                warn!(
+                    "repair: anchor node {} is missing from genesis initial stakers; \
+                     regenerate the genesis file, this cannot be auto-repaired",
+                    node_id
+                );
+            }
+            Drift::StuckNode {
+                node_id,
+                stale_key,
+                new_key,
+            } => {
+                info!(
+                    "repair: promoting stuck node {} from {} to {}",
+                    node_id, stale_key, new_key
+                );
+                let body = get_object(s3_cli, s3_bucket, stale_key).await?;
+                put_object(s3_cli, s3_bucket, new_key, body).await?;
+                delete_key(s3_cli, s3_bucket, stale_key).await?;
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn list_keys(s3_cli: &S3Client, s3_bucket: &str, prefix: &str) -> io::Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut continuation_token: Option<String> = None;
+    loop {
+        let mut req = s3_cli.list_objects_v2().bucket(s3_bucket).prefix(prefix);
+        if let Some(token) = &continuation_token {
+            req = req.continuation_token(token);
+        }
+        let resp = req.send().await.map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to list s3://{}/{} ({})", s3_bucket, prefix, e),
+            )
+        })?;
+
+        for obj in resp.contents.unwrap_or_default() {
+            if let Some(key) = obj.key {
+                keys.push(key);
+            }
+        }
+
+        if resp.is_truncated {
+            continuation_token = resp.next_continuation_token;
+        } else {
+            break;
+        }
+    }
+    Ok(keys)
+}
+
+async fn delete_key(s3_cli: &S3Client, s3_bucket: &str, key: &str) -> io::Result<()> {
+    s3_cli
+        .delete_object()
+        .bucket(s3_bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to delete s3://{}/{} ({})", s3_bucket, key, e),
+            )
+        })?;
+    Ok(())
+}
+
+async fn get_object(s3_cli: &S3Client, s3_bucket: &str, key: &str) -> io::Result<Vec<u8>> {
+    let resp = s3_cli
+        .get_object()
+        .bucket(s3_bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to get s3://{}/{} ({})", s3_bucket, key, e),
+            )
+        })?;
+    let body = resp.body.collect().await.map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to read s3://{}/{} body ({})", s3_bucket, key, e),
+        )
+    })?;
+    Ok(body.into_bytes().to_vec())
+}
+
+async fn put_object(s3_cli: &S3Client, s3_bucket: &str, key: &str, body: Vec<u8>) -> io::Result<()> {
+    s3_cli
+        .put_object()
+        .bucket(s3_bucket)
+        .key(key)
+        .body(aws_sdk_s3::types::ByteStream::from(body))
+        .send()
+        .await
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::Other,
+                format!("failed to put s3://{}/{} ({})", s3_bucket, key, e),
+            )
+        })?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use std::{
+    io::{self, Error, ErrorKind},
+    sync::Arc,
+    time::Duration,
+};
+
+use hyper::{Body, Client, Request};
+use hyper_rustls::HttpsConnectorBuilder;
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier},
+    Certificate, ClientConfig, RootCertStore, ServerName,
+};
+use tokio::time::timeout as tokio_timeout;
+
+/// Joins the base URL with the given path into a single URI string.
+pub fn join_uri(url: &str, path: &str) -> io::Result<String> {
+    Ok(format!(
+        "{}/{}",
+        url.trim_end_matches('/'),
+        path.trim_start_matches('/')
+    ))
+}
+
+/// Creates a simple "GET" request against "url/path".
+pub fn create_get(url: &str, path: &str) -> io::Result<Request<Body>> {
+    let uri = join_uri(url, path)?;
+    Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build request {}", e)))
+}
+
+/// Creates a "POST" request against "url/path" with a JSON-encoded body.
+pub fn create_post_json<T: serde::Serialize>(
+    url: &str,
+    path: &str,
+    body: &T,
+) -> io::Result<Request<Body>> {
+    let uri = join_uri(url, path)?;
+    let b = serde_json::to_vec(body)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to serialize body {}", e)))?;
+    Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(b))
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to build request {}", e)))
+}
+
+/// No-op certificate verifier that accepts any server certificate,
+/// reproducing "curl --insecure" for endpoints with self-signed certs
+/// (e.g. a freshly bootstrapped node's HTTPS health endpoint).
+struct NoCertVerifier;
+
+impl ServerCertVerifier for NoCertVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: std::time::SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Loads the default (verified) trust store from the OS native certificate
+/// store via "rustls-native-certs".
+fn tls_config_verified() -> io::Result<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    let certs = rustls_native_certs::load_native_certs().map_err(|e| {
+        Error::new(
+            ErrorKind::Other,
+            format!("failed to load native certs {}", e),
+        )
+    })?;
+    for cert in certs {
+        roots.add(&Certificate(cert.0)).map_err(|e| {
+            Error::new(ErrorKind::Other, format!("failed to add native cert {}", e))
+        })?;
+    }
+    Ok(ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+/// Builds a rustls config that skips certificate verification entirely,
+/// reproducing "curl --insecure".
+fn tls_config_insecure() -> ClientConfig {
+    let mut cfg = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(RootCertStore::empty())
+        .with_no_client_auth();
+    cfg.dangerous()
+        .set_certificate_verifier(Arc::new(NoCertVerifier));
+    cfg
+}
+
+/// Sends the request and reads the full response body.
+///
+/// HTTPS requests are routed through native rustls rather than shelling out
+/// to "curl": the default trust store comes from "rustls-native-certs", and
+/// setting `insecure` installs a no-op `ServerCertVerifier` to reproduce
+/// "curl --insecure" for self-signed endpoints. This gives real timeouts and
+/// typed errors instead of parsing subprocess stdout, and is the single code
+/// path for both "http://" and "https://" endpoints.
+pub async fn read_bytes(
+    req: Request<Body>,
+    dur: Duration,
+    https: bool,
+    insecure: bool,
+) -> io::Result<Vec<u8>> {
+    let fut = async {
+        let resp = if https {
+            let tls_config = if insecure {
+                tls_config_insecure()
+            } else {
+                tls_config_verified()?
+            };
+            let connector = HttpsConnectorBuilder::new()
+                .with_tls_config(tls_config)
+                .https_only()
+                .enable_http1()
+                .build();
+            Client::builder()
+                .build::<_, Body>(connector)
+                .request(req)
+                .await
+                .map_err(|e| {
+                    Error::new(ErrorKind::Other, format!("failed HTTPS request {}", e))
+                })?
+        } else {
+            Client::new()
+                .request(req)
+                .await
+                .map_err(|e| Error::new(ErrorKind::Other, format!("failed HTTP request {}", e)))?
+        };
+
+        let buf = hyper::body::to_bytes(resp.into_body())
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, format!("failed to read body {}", e)))?;
+        Ok(buf.to_vec())
+    };
+
+    match tokio_timeout(dur, fut).await {
+        Ok(result) => result,
+        Err(_) => Err(Error::new(
+            ErrorKind::TimedOut,
+            format!("request timed out after {:?}", dur),
+        )),
+    }
+}
+
+#[test]
+fn test_join_uri() {
+    assert_eq!(
+        join_uri("http://1.2.3.4:9650/", "/ext/health").unwrap(),
+        "http://1.2.3.4:9650/ext/health"
+    );
+    assert_eq!(
+        join_uri("https://1.2.3.4:9650", "ext/health").unwrap(),
+        "https://1.2.3.4:9650/ext/health"
+    );
+}
+
+/// "read_bytes(.., insecure: false)" is reachable (e.g. "benchmark::call"
+/// against a production RPC endpoint) even though every other caller in
+/// this tree currently passes "insecure: true"; exercise the verified
+/// config it builds so that path isn't dead code with zero coverage.
+#[test]
+fn test_tls_config_verified_builds() {
+    tls_config_verified().expect("failed to build verified TLS config from native certs");
+}
@@ -0,0 +1,220 @@
+use std::{
+    fs::File,
+    io::{self, Error, ErrorKind},
+    time::{Duration, Instant},
+};
+
+use aws_sdk_cloudformation::model::{Capability, OnFailure, Parameter, StackStatus, Tag};
+use avalanche_ops::aws::{self, cloudformation};
+use log::info;
+use serde::{Deserialize, Serialize};
+
+/// A single JSON-described workload file: a sequence of steps, optionally
+/// repeated and parameterized by region/node-count, so the same file can
+/// benchmark different cluster sizes.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorkloadFile {
+    #[serde(default)]
+    pub region: Option<String>,
+    #[serde(default)]
+    pub node_count: Option<u32>,
+    /// Repeats the whole "steps" block this many times (default 1).
+    #[serde(default = "default_repeat")]
+    pub repeat: u32,
+    pub steps: Vec<Step>,
+}
+
+fn default_repeat() -> u32 {
+    1
+}
+
+/// A single benchmarked operation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Step {
+    CreateStack {
+        stack_name: String,
+        template_body: String,
+        parameters: Vec<(String, String)>,
+    },
+    PollStack {
+        stack_name: String,
+        target_status: String,
+        timeout_secs: u64,
+        poll_interval_secs: u64,
+    },
+    HealthCheck {
+        endpoints: Vec<String>,
+        concurrency: u32,
+        timeout_secs: u64,
+    },
+}
+
+/// Wall-clock result for a single executed step.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StepResult {
+    pub step: String,
+    pub latency_ms: u128,
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Structured result of running a whole workload file, in the shape
+/// POSTed to a collector URL for cross-commit regression tracking.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BenchReport {
+    pub region: Option<String>,
+    pub node_count: Option<u32>,
+    pub results: Vec<StepResult>,
+}
+
+/// Loads a workload JSON file, executes each step (in order, `repeat`
+/// times) against a real or mocked AWS config, and records the wall-clock
+/// latency of each step.
+pub async fn run_workload(workload_file_path: &str) -> io::Result<BenchReport> {
+    let f = File::open(workload_file_path)?;
+    let workload: WorkloadFile = serde_json::from_reader(f)
+        .map_err(|e| Error::new(ErrorKind::InvalidInput, format!("invalid workload JSON: {}", e)))?;
+
+    let shared_config = aws::load_config(workload.region.clone()).await?;
+    let cloudformation_manager = cloudformation::Manager::new(&shared_config);
+
+    let mut results = Vec::new();
+    for round in 0..workload.repeat {
+        info!("running workload round {}/{}", round + 1, workload.repeat);
+        for step in &workload.steps {
+            results.push(run_step(&cloudformation_manager, step, workload.node_count).await);
+        }
+    }
+
+    Ok(BenchReport {
+        region: workload.region,
+        node_count: workload.node_count,
+        results,
+    })
+}
+
+async fn run_step(mgr: &cloudformation::Manager, step: &Step, node_count: Option<u32>) -> StepResult {
+    let started = Instant::now();
+    let (name, result): (String, io::Result<()>) = match step {
+        Step::CreateStack {
+            stack_name,
+            template_body,
+            parameters,
+        } => {
+            // lets the same workload file benchmark different cluster sizes
+            // by varying "node_count" alone, without hand-editing every
+            // step's "parameters", as long as the template declares a
+            // "NodeCount" parameter and the step doesn't already set one
+            let mut params: Vec<Parameter> = parameters
+                .iter()
+                .map(|(k, v)| {
+                    Parameter::builder()
+                        .parameter_key(k)
+                        .parameter_value(v)
+                        .build()
+                })
+                .collect();
+            if let Some(node_count) = node_count {
+                if !parameters.iter().any(|(k, _)| k == "NodeCount") {
+                    params.push(
+                        Parameter::builder()
+                            .parameter_key("NodeCount")
+                            .parameter_value(node_count.to_string())
+                            .build(),
+                    );
+                }
+            }
+            let ret = mgr
+                .create_stack(
+                    stack_name,
+                    Some(vec![Capability::CapabilityNamedIam]),
+                    OnFailure::Delete,
+                    template_body,
+                    Some(vec![Tag::builder().key("KIND").value("avalanche-ops-bench").build()]),
+                    Some(params),
+                )
+                .await;
+            (
+                format!("create_stack:{}", stack_name),
+                ret.map(|_| ()).map_err(|e| Error::new(ErrorKind::Other, e.to_string())),
+            )
+        }
+        Step::PollStack {
+            stack_name,
+            target_status,
+            timeout_secs,
+            poll_interval_secs,
+        } => {
+            let target = parse_stack_status(target_status);
+            let ret = mgr
+                .poll_stack(
+                    stack_name,
+                    target,
+                    Duration::from_secs(*timeout_secs),
+                    Duration::from_secs(*poll_interval_secs),
+                )
+                .await;
+            (
+                format!("poll_stack:{}", stack_name),
+                ret.map(|_| ()).map_err(|e| Error::new(ErrorKind::Other, e.to_string())),
+            )
+        }
+        Step::HealthCheck {
+            endpoints,
+            concurrency,
+            timeout_secs,
+        } => {
+            let ret = run_health_check(endpoints, *concurrency, Duration::from_secs(*timeout_secs)).await;
+            (
+                String::from("health_check"),
+                ret.map_err(|e| Error::new(ErrorKind::Other, e.to_string())),
+            )
+        }
+    };
+
+    StepResult {
+        step: name,
+        latency_ms: started.elapsed().as_millis(),
+        success: result.is_ok(),
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Polls "endpoints" in batches of "concurrency" at a time -- each batch
+/// checked fully in parallel via "health::poll_cluster", batches run one
+/// after another -- so "concurrency" actually bounds how many health
+/// checks are ever in flight at once, instead of padding out a timeout.
+/// "deadline" applies per batch.
+async fn run_health_check(endpoints: &[String], concurrency: u32, deadline: Duration) -> io::Result<()> {
+    let concurrency = (concurrency as usize).max(1);
+    for batch in endpoints.chunks(concurrency) {
+        avalanche_ops::aws::health::poll_cluster(
+            batch.to_vec(),
+            avalanche_ops::aws::health::ClusterCondition::AllHealthy,
+            deadline,
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+fn parse_stack_status(s: &str) -> StackStatus {
+    StackStatus::from(s)
+}
+
+/// POSTs a bench report to a collector URL so regressions in stack
+/// creation time or health-convergence time can be tracked across commits.
+pub async fn post_report(collector_url: &str, report: &BenchReport) -> io::Result<()> {
+    let body = serde_json::to_vec(report)
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to encode report: {}", e)))?;
+    reqwest::Client::new()
+        .post(collector_url)
+        .header("content-type", "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| Error::new(ErrorKind::Other, format!("failed to POST report: {}", e)))?;
+    Ok(())
+}
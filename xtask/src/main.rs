@@ -0,0 +1,52 @@
+mod bench;
+
+use std::io;
+
+use clap::{Parser, Subcommand};
+use log::info;
+
+/// cargo xtask bench --workload-file ./xtask/workloads/example.json
+#[derive(Debug, Parser)]
+#[command(name = "xtask")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs a JSON-described workload file and records per-step latency.
+    Bench {
+        #[arg(long)]
+        workload_file: String,
+        /// Optional URL to POST the resulting report to.
+        #[arg(long)]
+        collector_url: Option<String>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    env_logger::init_from_env(
+        env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+    );
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Bench {
+            workload_file,
+            collector_url,
+        } => {
+            let report = bench::run_workload(&workload_file).await?;
+            let out = serde_json::to_string_pretty(&report)?;
+            println!("{}", out);
+
+            if let Some(collector_url) = collector_url {
+                info!("posting report to {}", collector_url);
+                bench::post_report(&collector_url, &report).await?;
+            }
+        }
+    }
+
+    Ok(())
+}